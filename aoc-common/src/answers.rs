@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::{input, Error, ParseError, Part};
+
+/// Which of a day's inputs an answer belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum InputKind {
+    Example,
+    Real,
+}
+
+impl InputKind {
+    pub const ALL: [InputKind; 2] = [InputKind::Example, InputKind::Real];
+}
+
+impl fmt::Display for InputKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputKind::Example => f.pad("example"),
+            InputKind::Real => f.pad("real"),
+        }
+    }
+}
+
+impl FromStr for InputKind {
+    type Err = Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "example" => Ok(InputKind::Example),
+            "real" => Ok(InputKind::Real),
+            other => Err(Error::Invalid(format!("unknown input {:?}", other))),
+        }
+    }
+}
+
+/// The known-good answers for one day, read from its `answers.txt`.
+///
+/// Each non-comment line holds `<example|real> <part> <answer>`, e.g.
+/// `example 1 4361`.
+#[derive(Debug, Default)]
+pub struct Answers {
+    recorded: HashMap<(InputKind, Part), String>,
+}
+
+impl Answers {
+    /// A missing file is treated as a day with nothing recorded yet.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        if !path.as_ref().exists() {
+            return Ok(Answers::default());
+        }
+        let path = path.as_ref();
+        Answers::parse(&input::read_to_string(path)?).map_err(|e| e.in_file(path.display()))
+    }
+
+    pub fn parse(file: &str) -> Result<Self, Error> {
+        let mut recorded = HashMap::new();
+        for (i, line) in file.lines().enumerate() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.is_empty() || fields[0].starts_with('#') {
+                continue;
+            }
+            let [kind, part, answer] = fields[..] else {
+                let message = "expected `<example|real> <part> <answer>`";
+                return Err(ParseError::at(line, line.trim(), message)
+                    .on_line(i + 1)
+                    .into());
+            };
+            let kind = kind
+                .parse::<InputKind>()
+                .map_err(|_| ParseError::at(line, kind, "unknown input").on_line(i + 1))?;
+            let part = part
+                .parse::<Part>()
+                .map_err(|_| ParseError::at(line, part, "unknown part").on_line(i + 1))?;
+            recorded.insert((kind, part), answer.to_owned());
+        }
+        Ok(Answers { recorded })
+    }
+
+    pub fn get(&self, kind: InputKind, part: Part) -> Option<&str> {
+        self.recorded
+            .get(&(kind, part))
+            .map(|answer| answer.as_str())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.recorded.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(file: &str) -> ParseError {
+        match Answers::parse(file) {
+            Err(Error::Parse(e)) => e,
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_recorded_answers() {
+        let answers = Answers::parse("# input part answer\nexample 1 142\n\nreal 2 abc\n").unwrap();
+        assert_eq!(answers.get(InputKind::Example, Part::One), Some("142"));
+        assert_eq!(answers.get(InputKind::Real, Part::Two), Some("abc"));
+        assert_eq!(answers.get(InputKind::Real, Part::One), None);
+        assert!(Answers::parse("# nothing yet\n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn input_kinds_read_back_their_names() {
+        for kind in InputKind::ALL {
+            assert_eq!(kind.to_string().parse::<InputKind>().unwrap(), kind);
+        }
+        assert!("sample".parse::<InputKind>().is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        let error = parse_error("example 1 142\n  real 2\n");
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.text, "real 2");
+
+        let error = parse_error("sample 1 142\n");
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.message, "unknown input");
+
+        let error = parse_error("# input part answer\nreal 3 142\n");
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 6, "3"));
+        assert_eq!(error.message, "unknown part");
+    }
+}
@@ -0,0 +1,152 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Args, Parser};
+
+use crate::input::{self, Source};
+use crate::logging::{self, Verbosity};
+use crate::output::{self, Format};
+use crate::{Error, Part, Solver};
+
+/// Input selection shared by the day binaries and the `aoc` runner.
+#[derive(Debug, Default, Clone, Args)]
+pub struct InputArgs {
+    /// Puzzle input file, or `-` for stdin. Defaults to the day's part_1.txt
+    #[arg(short, long)]
+    pub input: Option<PathBuf>,
+
+    /// Use the day's bundled example input, or its part 2 example when only
+    /// part 2 is asked for and the day has one
+    #[arg(long, conflicts_with = "input")]
+    pub example: bool,
+}
+
+impl InputArgs {
+    /// Where to read the input of `part`, or of both parts when `None`.
+    pub fn source(&self, solver: &dyn Solver, part: Option<Part>) -> Result<Source, Error> {
+        if self.example {
+            return solver
+                .example_path(part.unwrap_or(Part::One))
+                .map(Source::File)
+                .ok_or(Error::NoExample(solver.day()));
+        }
+        Ok(match &self.input {
+            Some(path) if path.as_os_str() == "-" => Source::Stdin,
+            Some(path) => Source::File(path.clone()),
+            None => Source::File(solver.input_path()),
+        })
+    }
+}
+
+/// The options every day binary takes. Days with options of their own
+/// flatten this into their own parser and call [`main_with`].
+#[derive(Debug, Args)]
+pub struct DayArgs {
+    #[command(flatten)]
+    pub input: InputArgs,
+
+    #[arg(short, long)]
+    pub part: Option<Part>,
+
+    #[arg(long, value_enum, default_value_t)]
+    pub format: Format,
+
+    #[command(flatten)]
+    pub verbosity: Verbosity,
+}
+
+#[derive(Parser)]
+struct DayCli {
+    #[command(flatten)]
+    args: DayArgs,
+}
+
+/// Entry point for the per-day binaries.
+pub fn main(solver: &dyn Solver) -> ExitCode {
+    main_with(solver, &DayCli::parse().args)
+}
+
+/// Entry point for a day binary that has already parsed its arguments.
+pub fn main_with(solver: &dyn Solver, args: &DayArgs) -> ExitCode {
+    logging::init(args.verbosity);
+    match run(solver, args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Solves the requested parts, returning whether all of them were solved.
+fn run(solver: &dyn Solver, cli: &DayArgs) -> Result<bool, Error> {
+    let source = cli.input.source(solver, cli.part)?;
+    let mut lines = input::stream(&source)?;
+    let run = solver
+        .run_lines(&mut lines, &Part::select(cli.part))
+        .map_err(|e| e.in_file(&source))?
+        .in_file(&source);
+    output::print_run(&run, &source, cli.format);
+    Ok(run.solved())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::{Answer, Solution};
+
+    /// A day whose part 1 never has an answer, like day 5 given an odd
+    /// number of seeds.
+    struct Failing;
+
+    impl Solution for Failing {
+        type Input = ();
+
+        const DAY: u8 = 99;
+
+        const DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+        fn parse(&self, _input: &str) -> Result<Self::Input, Error> {
+            Ok(())
+        }
+
+        fn part1(&self, _input: &Self::Input) -> Result<Answer, Error> {
+            Err(Error::Invalid("no answer".to_owned()))
+        }
+
+        fn part2(&self, _input: &Self::Input) -> Result<Answer, Error> {
+            Ok(2.into())
+        }
+    }
+
+    #[test]
+    fn a_failed_part_fails_the_process() {
+        let path = std::env::temp_dir().join(format!("aoc-cli-{}.txt", std::process::id()));
+        fs::write(&path, "input\n").unwrap();
+        let exit = |part, format| {
+            let args = DayArgs {
+                input: InputArgs {
+                    input: Some(path.clone()),
+                    example: false,
+                },
+                part,
+                format,
+                verbosity: Verbosity::default(),
+            };
+            main_with(&Failing, &args)
+        };
+        let codes = [
+            exit(None, Format::Text),
+            exit(None, Format::Json),
+            exit(Some(Part::Two), Format::Text),
+        ];
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            codes,
+            [ExitCode::FAILURE, ExitCode::FAILURE, ExitCode::SUCCESS]
+        );
+    }
+}
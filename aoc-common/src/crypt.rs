@@ -0,0 +1,146 @@
+//! Encryption at rest for the real puzzle inputs, which may not be
+//! redistributed.
+//!
+//! An encrypted input sits next to where its plain text would be, with
+//! [`EXTENSION`] added to the name, and [`input::open`](crate::input::open)
+//! decrypts it on the fly. Files are sealed with ChaCha20-Poly1305 under a
+//! 256-bit key, given as 64 hex digits in the [`KEY_VAR`] environment
+//! variable or in the git-ignored [`key_file`] at the root of the repository.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+
+use crate::Error;
+
+/// The environment variable read before the key file.
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+
+/// Added to the name of a plain-text input to name its encrypted copy.
+pub const EXTENSION: &str = "enc";
+
+/// The first bytes of every encrypted file, followed by the nonce and the
+/// sealed contents.
+pub const MAGIC: &[u8] = b"aoc-enc1";
+
+const NONCE_LEN: usize = 12;
+
+/// Where the key is kept when [`KEY_VAR`] is not set.
+pub fn key_file() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("aoc-common sits inside the workspace")
+        .join(".input-key")
+}
+
+/// The encrypted copy of the input at `path`.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(EXTENSION);
+    PathBuf::from(name)
+}
+
+pub fn is_encrypted(contents: &[u8]) -> bool {
+    contents.starts_with(MAGIC)
+}
+
+pub struct Key(chacha20poly1305::Key);
+
+impl Key {
+    /// A fresh random key.
+    pub fn generate() -> Self {
+        Key(ChaCha20Poly1305::generate_key(&mut OsRng))
+    }
+
+    pub fn from_hex(text: &str) -> Result<Self, Error> {
+        let text = text.trim();
+        let invalid = || Error::Key("the key must be 64 hex digits".to_owned());
+        if text.len() != 64 || !text.is_ascii() {
+            return Err(invalid());
+        }
+        let mut key = chacha20poly1305::Key::default();
+        for (byte, pair) in key.iter_mut().zip(text.as_bytes().chunks(2)) {
+            let pair = std::str::from_utf8(pair).map_err(|_| invalid())?;
+            *byte = u8::from_str_radix(pair, 16).map_err(|_| invalid())?;
+        }
+        Ok(Key(key))
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    /// The key from [`KEY_VAR`], or else from the [`key_file`].
+    pub fn load() -> Result<Self, Error> {
+        if let Ok(text) = env::var(KEY_VAR) {
+            return Key::from_hex(&text)
+                .map_err(|e| Error::Key(format!("{} is invalid: {}", KEY_VAR, e)));
+        }
+        let path = key_file();
+        match fs::read_to_string(&path) {
+            Ok(text) => Key::from_hex(&text)
+                .map_err(|e| Error::Key(format!("{} is invalid: {}", path.display(), e))),
+            Err(_) => Err(Error::Key(format!(
+                "set {} or create {} with `aoc keygen`",
+                KEY_VAR,
+                path.display()
+            ))),
+        }
+    }
+
+    pub fn encrypt(&self, plain: &[u8]) -> Vec<u8> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let sealed = ChaCha20Poly1305::new(&self.0)
+            .encrypt(&nonce, plain)
+            .expect("inputs are far below the cipher's size limit");
+        [MAGIC, nonce.as_slice(), &sealed].concat()
+    }
+
+    /// The plain text of an encrypted file, or `None` if it was sealed with
+    /// another key or has been tampered with.
+    pub fn decrypt(&self, contents: &[u8]) -> Option<Vec<u8>> {
+        let rest = contents.strip_prefix(MAGIC)?;
+        if rest.len() < NONCE_LEN {
+            return None;
+        }
+        let (nonce, sealed) = rest.split_at(NONCE_LEN);
+        ChaCha20Poly1305::new(&self.0)
+            .decrypt(Nonce::from_slice(nonce), sealed)
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_and_rejects_other_keys() {
+        let key = Key::generate();
+        let sealed = key.encrypt(b"Time: 7 15 30\n");
+        assert!(is_encrypted(&sealed));
+        assert_eq!(key.decrypt(&sealed).unwrap(), b"Time: 7 15 30\n");
+        assert_eq!(Key::generate().decrypt(&sealed), None);
+        assert_eq!(key.decrypt(&sealed[..MAGIC.len() + 4]), None);
+    }
+
+    #[test]
+    fn hex_keys() {
+        let key = Key::generate();
+        assert_eq!(Key::from_hex(&key.to_hex()).unwrap().to_hex(), key.to_hex());
+        assert!(Key::from_hex("abc").is_err());
+        assert!(Key::from_hex(&"zz".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn encrypted_path_adds_extension() {
+        assert_eq!(
+            encrypted_path(Path::new("d01/part_1.txt")),
+            PathBuf::from("d01/part_1.txt.enc")
+        );
+    }
+}
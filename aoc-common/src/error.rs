@@ -0,0 +1,167 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use crate::Part;
+
+#[derive(Debug)]
+pub enum Error {
+    Io { path: PathBuf, source: io::Error },
+    Write { path: PathBuf, source: io::Error },
+    Parse(ParseError),
+    Invalid(String),
+    Http(String),
+    Key(String),
+    Decrypt(PathBuf),
+    Unsolved { day: u8, part: Part },
+    UnknownDay(u8),
+    NoExample(u8),
+    NoGenerator(u8),
+    InputNeedsDay,
+}
+
+impl Error {
+    /// Names the file a parse error came from; other errors are unchanged.
+    pub fn in_file<F: fmt::Display>(self, file: F) -> Self {
+        match self {
+            Error::Parse(e) => Error::Parse(e.in_file(file)),
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(f, "cannot read {}: {}", path.display(), source),
+            Error::Write { path, source } => {
+                write!(f, "cannot write {}: {}", path.display(), source)
+            }
+            Error::Parse(e) => write!(f, "{}", e),
+            Error::Invalid(message) => f.write_str(message),
+            Error::Http(message) => write!(f, "request failed: {}", message),
+            Error::Key(message) => write!(f, "no usable input key: {}", message),
+            Error::Decrypt(path) => write!(
+                f,
+                "cannot decrypt {}: wrong key or damaged file",
+                path.display()
+            ),
+            Error::Unsolved { day, part } => {
+                write!(f, "day {} part {} is not solved yet", day, part)
+            }
+            Error::UnknownDay(day) => write!(f, "no solution registered for day {}", day),
+            Error::NoExample(day) => write!(f, "day {} has no bundled example input", day),
+            Error::NoGenerator(day) => write!(f, "day {} has no input generator", day),
+            Error::InputNeedsDay => f.write_str("--input can only be used together with --day"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } | Error::Write { source, .. } => Some(source),
+            Error::Parse(e) => Some(e),
+            Error::Invalid(_)
+            | Error::Http(_)
+            | Error::Key(_)
+            | Error::Decrypt(_)
+            | Error::Unsolved { .. }
+            | Error::UnknownDay(_)
+            | Error::NoExample(_)
+            | Error::NoGenerator(_)
+            | Error::InputNeedsDay => None,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+/// A malformed token in a puzzle input. Lines and columns count from 1, the
+/// column being measured in characters.
+///
+/// Parsers usually only see a single line, so they build the error with
+/// [`ParseError::at`] and let their caller fill in the line number and file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new<T: Into<String>, M: Into<String>>(
+        line: usize,
+        column: usize,
+        text: T,
+        message: M,
+    ) -> Self {
+        ParseError {
+            file: None,
+            line,
+            column,
+            text: text.into(),
+            message: message.into(),
+        }
+    }
+
+    /// An error for `token`, which should be a subslice of `line` so the
+    /// column can be worked out from its position.
+    pub fn at<M: Into<String>>(line: &str, token: &str, message: M) -> Self {
+        ParseError {
+            file: None,
+            line: 1,
+            column: column_of(line, token),
+            text: token.to_owned(),
+            message: message.into(),
+        }
+    }
+
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+
+    /// Re-anchors an error raised while parsing `part`, a subslice of `line`,
+    /// so its column counts from the start of `line`.
+    pub fn within(mut self, line: &str, part: &str) -> Self {
+        self.column += column_of(line, part) - 1;
+        self
+    }
+
+    pub fn in_file<F: fmt::Display>(mut self, file: F) -> Self {
+        self.file.get_or_insert_with(|| file.to_string());
+        self
+    }
+}
+
+fn column_of(line: &str, token: &str) -> usize {
+    let start = line.as_ptr() as usize;
+    let offset = (token.as_ptr() as usize)
+        .checked_sub(start)
+        .filter(|&offset| offset + token.len() <= line.len())
+        .or_else(|| line.find(token))
+        .unwrap_or(0);
+    line[..offset].chars().count() + 1
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file)?;
+        }
+        write!(
+            f,
+            "{}:{}: {}: {:?}",
+            self.line, self.column, self.message, self.text
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
@@ -0,0 +1,313 @@
+//! Loading puzzle inputs.
+//!
+//! Every reader treats line endings the same way: `\n` and `\r\n` both end
+//! a line, no `\r` is left on the end of the last line, and the final line
+//! ending is optional. Blank lines are never silently turned into records:
+//! [`lines`] and [`grid`] drop the ones trailing the input, [`records`] skips
+//! them all for inputs of one record per line, and [`sections`] uses them as
+//! separators. Anywhere else a blank line is left for the day's parser to
+//! report.
+//!
+//! Files are opened through [`open`], which also reads the encrypted copy of
+//! an input (see [`crypt`]) and decompresses gzip.
+
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use flate2::bufread::MultiGzDecoder;
+
+use crate::{crypt, Error};
+
+/// The first two bytes of every gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Lines as the streaming loaders hand them out: each one may instead be
+/// the read error that ended the stream.
+pub type LineStream<'a> = dyn Iterator<Item = Result<String, Error>> + 'a;
+
+/// Where a day's puzzle text comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Stdin => f.write_str("<stdin>"),
+            Source::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+impl Source {
+    /// The path reported in read errors.
+    fn path(&self) -> PathBuf {
+        match self {
+            Source::Stdin => PathBuf::from("<stdin>"),
+            Source::File(path) => path.clone(),
+        }
+    }
+
+    fn io_error(&self, source: io::Error) -> Error {
+        Error::Io {
+            path: self.path(),
+            source,
+        }
+    }
+}
+
+/// Opens a source for buffered reading. A missing file is read from its
+/// encrypted copy if there is one. Encrypted and gzip-compressed input,
+/// recognised by its leading bytes rather than its name, is decrypted and
+/// decompressed on the fly.
+pub fn open(source: &Source) -> Result<Box<dyn BufRead>, Error> {
+    open_with_key(source, None)
+}
+
+/// Like [`open`], but decrypting with `key` if given rather than the one
+/// [`crypt::Key::load`] finds.
+pub(crate) fn open_with_key(
+    source: &Source,
+    key: Option<&crypt::Key>,
+) -> Result<Box<dyn BufRead>, Error> {
+    let reader: Box<dyn BufRead> = match source {
+        Source::Stdin => Box::new(io::stdin().lock()),
+        Source::File(path) => {
+            let encrypted = crypt::encrypted_path(path);
+            let stored = if !path.exists() && encrypted.exists() {
+                &encrypted
+            } else {
+                path
+            };
+            Box::new(BufReader::new(
+                fs::File::open(stored).map_err(|e| source.io_error(e))?,
+            ))
+        }
+    };
+    let reader = decrypt(reader, source, key)?;
+    decompress(reader).map_err(|e| source.io_error(e))
+}
+
+/// Reads and decrypts the whole stream if it starts like an encrypted file.
+fn decrypt<'a>(
+    mut reader: Box<dyn BufRead + 'a>,
+    source: &Source,
+    key: Option<&crypt::Key>,
+) -> Result<Box<dyn BufRead + 'a>, Error> {
+    let start = reader.fill_buf().map_err(|e| source.io_error(e))?;
+    if !crypt::is_encrypted(start) {
+        return Ok(reader);
+    }
+    let mut contents = Vec::new();
+    reader
+        .read_to_end(&mut contents)
+        .map_err(|e| source.io_error(e))?;
+    let loaded;
+    let key = match key {
+        Some(key) => key,
+        None => {
+            loaded = crypt::Key::load()?;
+            &loaded
+        }
+    };
+    let plain = key
+        .decrypt(&contents)
+        .ok_or_else(|| Error::Decrypt(source.path()))?;
+    Ok(Box::new(io::Cursor::new(plain)))
+}
+
+/// Wraps `reader` in a gzip decoder if the stream starts like one.
+fn decompress<'a>(mut reader: Box<dyn BufRead + 'a>) -> io::Result<Box<dyn BufRead + 'a>> {
+    if reader.fill_buf()?.starts_with(&GZIP_MAGIC) {
+        reader = Box::new(BufReader::new(MultiGzDecoder::new(reader)));
+    }
+    Ok(reader)
+}
+
+/// The lines of any buffered reader, without their line endings, one at a
+/// time. `source` only names the input in read errors.
+pub fn stream_lines<'a, R: BufRead + 'a>(
+    reader: R,
+    source: &Source,
+) -> impl Iterator<Item = Result<String, Error>> + 'a {
+    let source = source.clone();
+    reader.lines().map(move |line| match line {
+        Ok(line) => Ok(strip_cr(&line).to_owned()),
+        Err(e) => Err(source.io_error(e)),
+    })
+}
+
+/// The non-blank lines of a stream, each with its line number counting from
+/// 1, for inputs that hold one record per line.
+pub fn records<I>(lines: I) -> impl Iterator<Item = Result<(usize, String), Error>>
+where
+    I: Iterator<Item = Result<String, Error>>,
+{
+    lines
+        .enumerate()
+        .map(|(i, line)| line.map(|line| (i + 1, line)))
+        .filter(|line| !matches!(line, Ok((_, text)) if text.trim().is_empty()))
+}
+
+/// Removes the `\r` left behind by a `\r\n` line ending.
+fn strip_cr(line: &str) -> &str {
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+/// The lines of `file`, without the blank lines at its end.
+fn trimmed_lines(file: &str) -> impl Iterator<Item = &str> {
+    let lines: Vec<&str> = file.lines().map(strip_cr).collect();
+    let end = lines
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .map_or(0, |last| last + 1);
+    lines.into_iter().take(end)
+}
+
+/// Streams the lines of a file or stdin without reading it all first.
+pub fn stream(source: &Source) -> Result<Box<LineStream<'static>>, Error> {
+    Ok(Box::new(stream_lines(open(source)?, source)))
+}
+
+/// The lines of an in-memory file in the same shape as [`stream`] gives them.
+pub fn str_lines(file: &str) -> impl Iterator<Item = Result<String, Error>> + '_ {
+    file.lines().map(|line| Ok(strip_cr(line).to_owned()))
+}
+
+pub fn read_source(source: &Source) -> Result<String, Error> {
+    let mut file = String::new();
+    open(source)?
+        .read_to_string(&mut file)
+        .map_err(|e| source.io_error(e))?;
+    Ok(file)
+}
+
+pub fn read_to_string<P: AsRef<Path>>(path: P) -> Result<String, Error> {
+    read_source(&Source::File(path.as_ref().to_owned()))
+}
+
+/// Writes `contents` to `path`, creating any missing parent directories.
+pub fn write_file<P: AsRef<Path>>(path: P, contents: &str) -> Result<(), Error> {
+    let path = path.as_ref();
+    let write = || {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents)
+    };
+    write().map_err(|source| Error::Write {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+pub fn read_lines<P: AsRef<Path>>(path: P) -> Result<Vec<String>, Error> {
+    read_to_string(path).map(|file| lines(&file))
+}
+
+pub fn read_grid<P: AsRef<Path>>(path: P) -> Result<Vec<Vec<char>>, Error> {
+    read_to_string(path).map(|file| grid(&file))
+}
+
+pub fn read_sections<P: AsRef<Path>>(path: P) -> Result<Vec<Vec<String>>, Error> {
+    read_to_string(path).map(|file| sections(&file))
+}
+
+pub fn lines(file: &str) -> Vec<String> {
+    trimmed_lines(file).map(|line| line.to_owned()).collect()
+}
+
+pub fn grid(file: &str) -> Vec<Vec<char>> {
+    trimmed_lines(file)
+        .map(|line| line.chars().collect())
+        .collect()
+}
+
+/// Groups the lines of `file` into runs separated by one or more blank lines.
+pub fn sections(file: &str) -> Vec<Vec<String>> {
+    let mut sections: Vec<Vec<String>> = Vec::new();
+    let mut current: Vec<String> = Vec::new();
+    for line in file.lines().map(strip_cr) {
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                sections.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(line.to_owned());
+        }
+    }
+    if !current.is_empty() {
+        sections.push(current);
+    }
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    use super::*;
+
+    fn collect(bytes: Vec<u8>) -> Vec<String> {
+        let reader = decompress(Box::new(Cursor::new(bytes))).unwrap();
+        stream_lines(reader, &Source::Stdin)
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn streams_plain_lines() {
+        let lines = collect(b"Time: 7\r\nDistance: 9\n".to_vec());
+        assert_eq!(lines, ["Time: 7", "Distance: 9"]);
+    }
+
+    #[test]
+    fn streams_gzip_lines() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"two1nine\neightwothree").unwrap();
+        let lines = collect(encoder.finish().unwrap());
+        assert_eq!(lines, ["two1nine", "eightwothree"]);
+    }
+
+    #[test]
+    fn lines_drop_only_trailing_blank_lines() {
+        assert_eq!(lines("a\r\n\r\nb\r\n\r\n \n"), ["a", "", "b"]);
+        assert_eq!(grid("ab\rcd\r\n\n"), [vec!['a', 'b', '\r', 'c', 'd']]);
+    }
+
+    #[test]
+    fn records_skip_blank_lines_but_keep_numbers() {
+        let records: Vec<(usize, String)> = records(str_lines("Game 1\r\n\r\n  \nGame 2\n\n"))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            records,
+            [(1, "Game 1".to_owned()), (4, "Game 2".to_owned())]
+        );
+    }
+
+    #[test]
+    fn reads_the_encrypted_copy_of_a_missing_file() {
+        let key = crypt::Key::generate();
+        let path = std::env::temp_dir().join(format!("aoc-input-{}.txt", std::process::id()));
+        let encrypted = crypt::encrypted_path(&path);
+        fs::write(&encrypted, key.encrypt(b"Time: 7\nDistance: 9\n")).unwrap();
+
+        // The key is passed in rather than set in the process-wide environment
+        let reader = open_with_key(&Source::File(path), Some(&key));
+        fs::remove_file(&encrypted).unwrap();
+        let mut file = String::new();
+        reader.unwrap().read_to_string(&mut file).unwrap();
+        assert_eq!(file, "Time: 7\nDistance: 9\n");
+    }
+}
@@ -0,0 +1,22 @@
+//! Helpers shared by every day of the 2023 puzzles: loading the input files,
+//! splitting them into lines, character grids or blank-line separated
+//! sections, decrypting inputs kept encrypted at rest, the [`Solution`] trait
+//! each day implements, the recorded [`Answers`] used to check them and the
+//! seeded [`Rng`] behind the input generators.
+
+mod answers;
+pub mod cli;
+pub mod crypt;
+mod error;
+pub mod generate;
+pub mod input;
+pub mod logging;
+pub mod output;
+mod solution;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+pub use answers::{Answers, InputKind};
+pub use error::{Error, ParseError};
+pub use generate::Rng;
+pub use solution::{Answer, Part, PartRun, Run, Solution, Solver};
@@ -0,0 +1,88 @@
+//! Diagnostics for the solvers and the runner, written to stderr through the
+//! `log` macros so they never mix with the answers on stdout.
+
+use std::io::Write;
+
+use clap::Args;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+/// The `-v`/`-q` flags shared by the day binaries and the runner.
+#[derive(Debug, Default, Clone, Copy, Args)]
+pub struct Verbosity {
+    /// Print progress (-v), debugging detail (-vv) or every trace (-vvv) to stderr
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    pub verbose: u8,
+
+    /// Only print errors
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+}
+
+impl Verbosity {
+    pub fn level(&self) -> LevelFilter {
+        if self.quiet {
+            return LevelFilter::Error;
+        }
+        match self.verbose {
+            0 => LevelFilter::Warn,
+            1 => LevelFilter::Info,
+            2 => LevelFilter::Debug,
+            _ => LevelFilter::Trace,
+        }
+    }
+}
+
+struct StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let level = match record.level() {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        };
+        // A closed stderr leaves nowhere to report the failure
+        let _ = writeln!(
+            std::io::stderr().lock(),
+            "[{} {}] {}",
+            level,
+            record.target(),
+            record.args()
+        );
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: StderrLogger = StderrLogger;
+
+/// Installs the stderr logger at the level picked by `verbosity`. Later calls
+/// only change the level.
+pub fn init(verbosity: Verbosity) {
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(verbosity.level());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_pick_level() {
+        let level = |verbose, quiet| Verbosity { verbose, quiet }.level();
+        assert_eq!(level(0, false), LevelFilter::Warn);
+        assert_eq!(level(1, false), LevelFilter::Info);
+        assert_eq!(level(2, false), LevelFilter::Debug);
+        assert_eq!(level(5, false), LevelFilter::Trace);
+        assert_eq!(level(0, true), LevelFilter::Error);
+    }
+}
@@ -0,0 +1,76 @@
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::input::Source;
+use crate::Run;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// One labelled line per part
+    #[default]
+    Text,
+    /// One JSON object per part, one per line
+    Json,
+}
+
+/// A single part's result as emitted by `--format json`.
+#[derive(Debug, Serialize)]
+pub struct PartRecord<'a> {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<&'a str>,
+    pub elapsed_ms: f64,
+    pub input_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+pub fn records<'a>(run: &'a Run, source: &Source) -> Vec<PartRecord<'a>> {
+    run.parts
+        .iter()
+        .map(|part| PartRecord {
+            day: run.day,
+            part: part.part.number(),
+            answer: part.answer.as_ref().ok().map(|answer| answer.as_str()),
+            elapsed_ms: part.elapsed.as_secs_f64() * 1000.0,
+            input_path: source.to_string(),
+            error: part.answer.as_ref().err().map(|e| e.to_string()),
+        })
+        .collect()
+}
+
+/// Prints the answers on stdout and the errors of any failed parts on
+/// stderr; a JSON record also holds its part's error.
+pub fn print_run(run: &Run, source: &Source, format: Format) {
+    match format {
+        Format::Text => print_text(run),
+        Format::Json => {
+            for record in records(run, source) {
+                println!(
+                    "{}",
+                    serde_json::to_string(&record).expect("part records always serialize")
+                );
+            }
+            for part in &run.parts {
+                if let Err(e) = &part.answer {
+                    eprintln!("Day {:02} part {}: {}", run.day, part.part, e);
+                }
+            }
+        }
+    }
+}
+
+fn print_text(run: &Run) {
+    for part in &run.parts {
+        match &part.answer {
+            Ok(answer) => println!(
+                "Day {:02} part {}: {} ({:.3} ms)",
+                run.day,
+                part.part,
+                answer,
+                part.elapsed.as_secs_f64() * 1000.0
+            ),
+            Err(e) => eprintln!("Day {:02} part {}: {}", run.day, part.part, e),
+        }
+    }
+}
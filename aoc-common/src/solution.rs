@@ -0,0 +1,250 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::input::LineStream;
+use crate::{Error, Rng};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    /// The parts to run when the user may or may not have picked one.
+    pub fn select(part: Option<Part>) -> Vec<Part> {
+        match part {
+            Some(part) => vec![part],
+            None => Part::ALL.to_vec(),
+        }
+    }
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&self.number().to_string())
+    }
+}
+
+impl FromStr for Part {
+    type Err = Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string.trim() {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            other => Err(Error::Invalid(format!("unknown part {:?}", other))),
+        }
+    }
+}
+
+/// The printable result of a single part. Days produce different integer
+/// types, so the answer is kept in its rendered form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer(String);
+
+impl Answer {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+macro_rules! answer_from {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(value: $t) -> Self {
+                Answer(value.to_string())
+            }
+        })*
+    };
+}
+
+answer_from!(i32, i64, u32, u64, usize, String, &str);
+
+/// Implemented by every day: turn the raw puzzle text into `Input` once, then
+/// answer each part from it.
+pub trait Solution {
+    type Input;
+
+    const DAY: u8;
+
+    /// Directory holding the day's input files, normally the day crate's
+    /// `CARGO_MANIFEST_DIR`.
+    const DIR: &'static str;
+
+    /// File name of the bundled example input, relative to `DIR`.
+    const EXAMPLE: Option<&'static str> = None;
+
+    /// File name of a separate part 2 example, for puzzles that give one.
+    /// Part 2 uses [`Solution::EXAMPLE`] otherwise.
+    const EXAMPLE_2: Option<&'static str> = None;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error>;
+
+    /// Parses the input as it is read, one line at a time. The default
+    /// gathers the lines and hands them to [`Solution::parse`]; days whose
+    /// input is line by line override it so a large input is never held in
+    /// memory as a whole.
+    fn parse_lines(&self, lines: &mut LineStream) -> Result<Self::Input, Error> {
+        let mut file = String::new();
+        for line in lines {
+            file.push_str(&line?);
+            file.push('\n');
+        }
+        self.parse(&file)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, Error>;
+
+    fn part2(&self, _input: &Self::Input) -> Result<Answer, Error> {
+        Err(Error::Unsolved {
+            day: Self::DAY,
+            part: Part::Two,
+        })
+    }
+
+    /// A random but valid puzzle input. What `size` counts (lines, games,
+    /// grid side, ...) is up to the day.
+    fn generate(&self, _rng: &mut Rng, _size: usize) -> Result<String, Error> {
+        Err(Error::NoGenerator(Self::DAY))
+    }
+}
+
+#[derive(Debug)]
+pub struct PartRun {
+    pub part: Part,
+    pub answer: Result<Answer, Error>,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug)]
+pub struct Run {
+    pub day: u8,
+    pub parse: Duration,
+    pub parts: Vec<PartRun>,
+}
+
+impl Run {
+    /// Whether every part produced an answer.
+    pub fn solved(&self) -> bool {
+        self.parts.iter().all(|part| part.answer.is_ok())
+    }
+
+    /// Names the input in the parse errors of any failed part, as
+    /// [`Error::in_file`] does for the run as a whole.
+    pub fn in_file<F: fmt::Display>(mut self, file: F) -> Self {
+        self.parts = self
+            .parts
+            .into_iter()
+            .map(|part| PartRun {
+                answer: part.answer.map_err(|e| e.in_file(&file)),
+                ..part
+            })
+            .collect();
+        self
+    }
+}
+
+/// Object-safe view of a [`Solution`] so the runner can keep every day in one
+/// table.
+pub trait Solver: Sync {
+    fn day(&self) -> u8;
+
+    /// The real puzzle input, `part_1.txt` in the day's directory.
+    fn input_path(&self) -> PathBuf;
+
+    /// The bundled example input of `part`.
+    fn example_path(&self, part: Part) -> Option<PathBuf>;
+
+    /// The recorded answers for the day, `answers.txt` in its directory.
+    fn answers_path(&self) -> PathBuf;
+
+    fn run(&self, input: &str, parts: &[Part]) -> Result<Run, Error>;
+
+    /// Like [`Solver::run`], but parsing the lines as they are streamed in.
+    fn run_lines(&self, lines: &mut LineStream, parts: &[Part]) -> Result<Run, Error>;
+
+    /// A puzzle input generated from `seed`; the same seed and size always
+    /// give the same text.
+    fn generate(&self, seed: u64, size: usize) -> Result<String, Error>;
+}
+
+impl<S: Solution + Sync> Solver for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn input_path(&self) -> PathBuf {
+        Path::new(S::DIR).join("part_1.txt")
+    }
+
+    fn example_path(&self, part: Part) -> Option<PathBuf> {
+        let name = match part {
+            Part::One => S::EXAMPLE,
+            Part::Two => S::EXAMPLE_2.or(S::EXAMPLE),
+        };
+        name.map(|name| Path::new(S::DIR).join(name))
+    }
+
+    fn answers_path(&self) -> PathBuf {
+        Path::new(S::DIR).join("answers.txt")
+    }
+
+    fn run(&self, input: &str, parts: &[Part]) -> Result<Run, Error> {
+        let start = Instant::now();
+        let parsed = self.parse(input)?;
+        Ok(solve(self, &parsed, start.elapsed(), parts))
+    }
+
+    fn run_lines(&self, lines: &mut LineStream, parts: &[Part]) -> Result<Run, Error> {
+        let start = Instant::now();
+        let parsed = self.parse_lines(lines)?;
+        Ok(solve(self, &parsed, start.elapsed(), parts))
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Result<String, Error> {
+        Solution::generate(self, &mut Rng::new(seed), size)
+    }
+}
+
+/// Times each requested part on the already parsed input.
+fn solve<S: Solution>(solution: &S, parsed: &S::Input, parse: Duration, parts: &[Part]) -> Run {
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = match part {
+                Part::One => solution.part1(parsed),
+                Part::Two => solution.part2(parsed),
+            };
+            PartRun {
+                part,
+                answer,
+                elapsed: start.elapsed(),
+            }
+        })
+        .collect();
+
+    Run {
+        day: S::DAY,
+        parse,
+        parts,
+    }
+}
@@ -0,0 +1,48 @@
+//! Helpers for the days' tests.
+
+use std::io::Cursor;
+
+use crate::input::{self, Source};
+use crate::{Part, Solver};
+
+/// `file` rewritten with each style of line ending a hand-edited input may
+/// have, labelled for assertion messages.
+pub fn line_endings(file: &str) -> Vec<(&'static str, String)> {
+    let lines: Vec<&str> = file.lines().collect();
+    let lf = lines.join("\n");
+    let crlf = lines.join("\r\n");
+    vec![
+        ("LF", format!("{}\n", lf)),
+        ("CRLF", format!("{}\r\n", crlf)),
+        ("LF, no final newline", lf.clone()),
+        ("CRLF, no final newline", crlf.clone()),
+        ("LF, trailing blank lines", format!("{}\n\n\n", lf)),
+        (
+            "CRLF, trailing blank lines",
+            format!("{}\r\n\r\n\r\n", crlf),
+        ),
+    ]
+}
+
+/// Checks that `solver` gives the expected answers for every variant of
+/// `file` from [`line_endings`], both parsing the whole text and streaming it.
+pub fn assert_answers(solver: &dyn Solver, file: &str, expected: &[(Part, &str)]) {
+    let parts: Vec<Part> = expected.iter().map(|&(part, _)| part).collect();
+    for (name, variant) in line_endings(file) {
+        let whole = solver.run(&variant, &parts);
+        let mut lines = input::stream_lines(Cursor::new(variant.as_bytes()), &Source::Stdin);
+        let streamed = solver.run_lines(&mut lines, &parts);
+
+        for (how, run) in [("parsed", whole), ("streamed", streamed)] {
+            let run = run.unwrap_or_else(|e| panic!("{} ({}): {}", name, how, e));
+            for (part_run, &(part, answer)) in run.parts.iter().zip(expected) {
+                let got = part_run
+                    .answer
+                    .as_ref()
+                    .map(|answer| answer.as_str())
+                    .map_err(|e| e.to_string());
+                assert_eq!(got, Ok(answer), "{} ({}) part {}", name, how, part);
+            }
+        }
+    }
+}
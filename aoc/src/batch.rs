@@ -0,0 +1,172 @@
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use aoc_common::input::{self, Source};
+use aoc_common::{Error, Part, Run, Solver};
+
+use crate::stress;
+use crate::timing::millis;
+
+pub struct Entry {
+    pub file: String,
+    pub run: Result<Run, Error>,
+}
+
+/// The regular, non-hidden files directly inside `dir`, sorted by name.
+pub fn input_files(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    let io_error = |source| Error::Io {
+        path: dir.to_owned(),
+        source,
+    };
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir).map_err(io_error)? {
+        let entry = entry.map_err(io_error)?;
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        if !hidden && entry.file_type().map_err(io_error)?.is_file() {
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Solves every file on a pool of worker threads, one per core, keeping the
+/// entries in the order of `files`.
+pub fn run_all(solver: &dyn Solver, files: &[PathBuf], parts: &[Part]) -> Vec<Entry> {
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<Entry>>> = Mutex::new(files.iter().map(|_| None).collect());
+    let workers = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(files.len());
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(path) = files.get(i) else {
+                    break;
+                };
+                let file = path.file_name().map_or_else(
+                    || path.display().to_string(),
+                    |name| name.to_string_lossy().into_owned(),
+                );
+                let source = Source::File(path.clone());
+                let run = input::stream(&source).and_then(|mut lines| {
+                    // A solver panicking on one file should not sink the rest
+                    panic::catch_unwind(AssertUnwindSafe(|| solver.run_lines(&mut lines, parts)))
+                        .unwrap_or_else(|panic| Err(Error::Invalid(stress::panic_message(&*panic))))
+                });
+                let run = run.map_err(|e| e.in_file(&file));
+                results.lock().unwrap()[i] = Some(Entry { file, run });
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|entry| entry.expect("every file was run"))
+        .collect()
+}
+
+/// Prints one row per file with each part's answer and time, then the errors
+/// behind any `ERROR` cells. Returns whether every file solved cleanly.
+pub fn print_table(entries: &[Entry], parts: &[Part]) -> bool {
+    let mut errors = Vec::new();
+    let rows: Vec<Vec<String>> = entries
+        .iter()
+        .map(|entry| {
+            let mut row = vec![entry.file.clone()];
+            match &entry.run {
+                Ok(run) => {
+                    row.push(millis(run.parse));
+                    for part in &run.parts {
+                        match &part.answer {
+                            Ok(answer) => row.push(answer.to_string()),
+                            Err(e) => {
+                                errors.push(format!("{} part {}: {}", entry.file, part.part, e));
+                                row.push("ERROR".to_owned());
+                            }
+                        }
+                        row.push(millis(part.elapsed));
+                    }
+                }
+                Err(e) => {
+                    // Parse errors already name their file
+                    errors.push(match e {
+                        Error::Parse(_) => e.to_string(),
+                        e => format!("{}: {}", entry.file, e),
+                    });
+                    row.push("ERROR".to_owned());
+                }
+            }
+            row
+        })
+        .collect();
+
+    let mut header = vec!["file".to_owned(), "parse ms".to_owned()];
+    for part in parts {
+        header.push(format!("part {}", part));
+        header.push("ms".to_owned());
+    }
+    let widths: Vec<usize> = (0..header.len())
+        .map(|column| {
+            rows.iter()
+                .chain([&header])
+                .filter_map(|row| row.get(column).map(String::len))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    for row in [&header].into_iter().chain(&rows) {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{:<width$}", cell))
+            .collect();
+        println!("{}", cells.join("  ").trim_end());
+    }
+
+    if !errors.is_empty() {
+        println!();
+        for error in &errors {
+            println!("{}", error);
+        }
+    }
+    errors.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_every_file_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("b.txt"), "Time: 7\nDistance: 9").unwrap();
+        fs::write(dir.path().join("a.txt"), "Time: 30\nDistance: 200").unwrap();
+        fs::write(dir.path().join("c.txt"), "Time: x\nDistance: 9").unwrap();
+        fs::write(dir.path().join(".hidden"), "").unwrap();
+
+        let files = input_files(dir.path()).unwrap();
+        let entries = run_all(&d06::Day06, &files, &[Part::One]);
+        let names: Vec<&str> = entries.iter().map(|entry| entry.file.as_str()).collect();
+        assert_eq!(names, ["a.txt", "b.txt", "c.txt"]);
+
+        let answer = |entry: &Entry| {
+            entry.run.as_ref().unwrap().parts[0]
+                .answer
+                .as_ref()
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(answer(&entries[0]), "9");
+        assert_eq!(answer(&entries[1]), "4");
+        let error = entries[2].run.as_ref().err().unwrap().to_string();
+        assert!(error.starts_with("c.txt:1:"), "{}", error);
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::time::Duration;
+
+use aoc_common::{input, Error, ParseError, Part, Solver};
+
+use crate::timing::millis;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stage {
+    Parse,
+    Part(Part),
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Parse => f.pad("parse"),
+            Stage::Part(part) => f.pad(&format!("part{}", part)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+pub struct Bench {
+    pub day: u8,
+    pub stage: Stage,
+    pub stats: Stats,
+    pub bytes: usize,
+}
+
+/// Times `runs` full runs of `solver` over `file`. Parts that fail are left
+/// out rather than timed.
+pub fn bench_day(solver: &dyn Solver, file: &str, runs: usize) -> Result<Vec<Bench>, Error> {
+    let mut samples: Vec<(Stage, Vec<Duration>)> = Vec::new();
+    for _ in 0..runs.max(1) {
+        let run = solver.run(file, &Part::ALL)?;
+        let timings = std::iter::once((Stage::Parse, run.parse)).chain(
+            run.parts
+                .iter()
+                .filter(|part| part.answer.is_ok())
+                .map(|part| (Stage::Part(part.part), part.elapsed)),
+        );
+        for (stage, elapsed) in timings {
+            match samples.iter_mut().find(|(seen, _)| *seen == stage) {
+                Some((_, durations)) => durations.push(elapsed),
+                None => samples.push((stage, vec![elapsed])),
+            }
+        }
+    }
+
+    Ok(samples
+        .into_iter()
+        .map(|(stage, durations)| Bench {
+            day: solver.day(),
+            stage,
+            stats: Stats::from_samples(durations),
+            bytes: file.len(),
+        })
+        .collect())
+}
+
+/// Median timings from an earlier run, keyed by day and stage name.
+pub type Baseline = HashMap<(u8, String), Duration>;
+
+pub fn save<P: AsRef<Path>>(path: P, benches: &[Bench]) -> Result<(), Error> {
+    let mut file = String::from("# day stage min_ns median_ns max_ns\n");
+    for bench in benches {
+        file += &format!(
+            "{} {} {} {} {}\n",
+            bench.day,
+            bench.stage,
+            bench.stats.min.as_nanos(),
+            bench.stats.median.as_nanos(),
+            bench.stats.max.as_nanos()
+        );
+    }
+    input::write_file(path, &file)
+}
+
+pub fn load_baseline<P: AsRef<Path>>(path: P) -> Result<Baseline, Error> {
+    let path = path.as_ref();
+    let file = input::read_to_string(path)?;
+    let mut baseline = Baseline::new();
+    for (i, line) in file.lines().enumerate() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.is_empty() || fields[0].starts_with('#') {
+            continue;
+        }
+        let invalid = || {
+            Error::from(
+                ParseError::at(line, line, "expected `<day> <stage> <min> <median> <max>`")
+                    .on_line(i + 1)
+                    .in_file(path.display()),
+            )
+        };
+        let [day, stage, _, median, _] = fields[..] else {
+            return Err(invalid());
+        };
+        let day = day.parse::<u8>().map_err(|_| invalid())?;
+        let median = median.parse::<u64>().map_err(|_| invalid())?;
+        baseline.insert((day, stage.to_owned()), Duration::from_nanos(median));
+    }
+    Ok(baseline)
+}
+
+/// Prints the timings, flagging stages whose median grew by more than
+/// `threshold` percent over the baseline. Returns how many regressed.
+pub fn print_table(benches: &[Bench], baseline: Option<&Baseline>, threshold: f64) -> usize {
+    let mut regressions = 0;
+    println!(
+        "{:<4} {:<6} {:>12} {:>12} {:>12} {:>12}  baseline",
+        "day", "stage", "min ms", "median ms", "max ms", "MB/s"
+    );
+    for bench in benches {
+        let median = bench.stats.median.as_secs_f64();
+        let throughput = if median > 0.0 {
+            format!("{:.1}", bench.bytes as f64 / median / 1_000_000.0)
+        } else {
+            "-".to_owned()
+        };
+        let comparison = baseline
+            .and_then(|baseline| baseline.get(&(bench.day, bench.stage.to_string())))
+            .map(|previous| {
+                let change = (median / previous.as_secs_f64().max(f64::EPSILON) - 1.0) * 100.0;
+                if change > threshold {
+                    regressions += 1;
+                    format!("{:+.1}% REGRESSION", change)
+                } else {
+                    format!("{:+.1}%", change)
+                }
+            })
+            .unwrap_or_default();
+        println!(
+            "{:<4} {:<6} {:>12} {:>12} {:>12} {:>12}  {}",
+            format!("{:02}", bench.day),
+            bench.stage,
+            millis(bench.stats.min),
+            millis(bench.stats.median),
+            millis(bench.stats.max),
+            throughput,
+            comparison
+        );
+    }
+    regressions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bench(stage: Stage, median_ms: u64) -> Bench {
+        let median = Duration::from_millis(median_ms);
+        Bench {
+            day: 6,
+            stage,
+            stats: Stats {
+                min: median / 2,
+                median,
+                max: median * 2,
+            },
+            bytes: 100,
+        }
+    }
+
+    #[test]
+    fn saved_timings_load_back_as_a_baseline() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bench.txt");
+        let benches = [bench(Stage::Parse, 3), bench(Stage::Part(Part::Two), 40)];
+        save(&path, &benches).unwrap();
+
+        let baseline = load_baseline(&path).unwrap();
+        assert_eq!(baseline.len(), 2);
+        assert_eq!(baseline[&(6, "parse".to_owned())], Duration::from_millis(3));
+        assert_eq!(
+            baseline[&(6, "part2".to_owned())],
+            Duration::from_millis(40)
+        );
+
+        input::write_file(&path, "# day stage min_ns median_ns max_ns\n6 parse 1 2\n").unwrap();
+        let error = load_baseline(&path).unwrap_err().to_string();
+        assert!(
+            error.ends_with(":2:1: expected `<day> <stage> <min> <median> <max>`: \"6 parse 1 2\""),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn regressions_are_counted_past_the_threshold() {
+        let baseline = Baseline::from([
+            ((6, "parse".to_owned()), Duration::from_millis(10)),
+            ((6, "part1".to_owned()), Duration::from_millis(10)),
+        ]);
+        // 10% and 30% slower, with part 2 missing from the baseline
+        let benches = [
+            bench(Stage::Parse, 11),
+            bench(Stage::Part(Part::One), 13),
+            bench(Stage::Part(Part::Two), 100),
+        ];
+        assert_eq!(print_table(&benches, Some(&baseline), 20.0), 1);
+        assert_eq!(print_table(&benches, Some(&baseline), 5.0), 2);
+        assert_eq!(print_table(&benches, Some(&baseline), 50.0), 0);
+        assert_eq!(print_table(&benches, None, 0.0), 0);
+    }
+}
@@ -0,0 +1,220 @@
+use std::env;
+use std::path::{Path, PathBuf};
+
+use aoc_common::{crypt, input, Error, Part};
+use clap::Args;
+
+pub const YEAR: u16 = 2023;
+
+/// How to reach the puzzle site. The base URL can point at a local stand-in
+/// server for testing.
+#[derive(Debug, Clone, Args)]
+pub struct ClientArgs {
+    #[arg(long, env = "AOC_BASE_URL", default_value = "https://adventofcode.com")]
+    pub base_url: String,
+
+    /// Session cookie value; read from --session-file when not set
+    #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+    pub session: Option<String>,
+
+    /// File holding the session cookie, defaults to ~/.config/aoc/session
+    #[arg(long)]
+    pub session_file: Option<PathBuf>,
+}
+
+impl ClientArgs {
+    pub fn client(&self) -> Result<Client, Error> {
+        let session = match &self.session {
+            Some(session) => session.clone(),
+            None => {
+                let path = self
+                    .session_file
+                    .clone()
+                    .or_else(default_session_file)
+                    .ok_or_else(|| {
+                        Error::Invalid("set AOC_SESSION or pass --session-file".to_owned())
+                    })?;
+                input::read_to_string(path)?.trim().to_owned()
+            }
+        };
+        Ok(Client::new(&self.base_url, &session))
+    }
+}
+
+fn default_session_file() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| Path::new(&home).join(".config/aoc/session"))
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .build()
+            .into();
+        Client {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session: session.to_owned(),
+            agent,
+        }
+    }
+
+    fn url(&self, day: u8, page: &str) -> String {
+        format!("{}/{}/day/{}{}", self.base_url, YEAR, day, page)
+    }
+
+    pub fn fetch_input(&self, day: u8) -> Result<String, Error> {
+        let url = self.url(day, "/input");
+        let response = self
+            .agent
+            .get(&url)
+            .header("Cookie", format!("session={}", self.session))
+            .header("User-Agent", USER_AGENT)
+            .call();
+        read_body(&url, response)
+    }
+
+    /// Posts `answer` for `part` and returns the page the site answers with.
+    pub fn submit_answer(&self, day: u8, part: Part, answer: &str) -> Result<String, Error> {
+        let url = self.url(day, "/answer");
+        let level = part.to_string();
+        let response = self
+            .agent
+            .post(&url)
+            .header("Cookie", format!("session={}", self.session))
+            .header("User-Agent", USER_AGENT)
+            .send_form([("level", level.as_str()), ("answer", answer)]);
+        read_body(&url, response)
+    }
+}
+
+fn read_body(
+    url: &str,
+    response: Result<ureq::http::Response<ureq::Body>, ureq::Error>,
+) -> Result<String, Error> {
+    let mut response = response.map_err(|e| Error::Http(format!("{}: {}", url, e)))?;
+    let body = response
+        .body_mut()
+        .read_to_string()
+        .map_err(|e| Error::Http(format!("{}: {}", url, e)))?;
+    if !response.status().is_success() {
+        return Err(Error::Http(format!(
+            "{} returned {}: {}",
+            url,
+            response.status(),
+            body.trim()
+        )));
+    }
+    Ok(body)
+}
+
+const USER_AGENT: &str = "github.com/roganjoshp/AOC2023 input fetcher";
+
+/// Where the input for `day` is cached, matching what the day crates read.
+pub fn input_path(root: &Path, day: u8) -> PathBuf {
+    root.join(format!("d{:02}", day)).join("part_1.txt")
+}
+
+/// Downloads the input for `day` into `path` unless it, or its encrypted copy,
+/// is already there. An empty file, as left by `aoc new`, does not count as
+/// cached. Returns whether a download happened.
+pub fn fetch_cached(args: &ClientArgs, day: u8, path: &Path) -> Result<bool, Error> {
+    let on_disk = |path: &Path| path.metadata().is_ok_and(|meta| meta.len() > 0);
+    if on_disk(path) || on_disk(&crypt::encrypted_path(path)) {
+        return Ok(false);
+    }
+    let body = args.client()?.fetch_input(day)?;
+    input::write_file(path, &body)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    /// A stand-in for the puzzle site answering every request with `status`
+    /// and `body`. Returns its base URL and the requests it has seen.
+    pub fn serve(status: u16, body: &'static str) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(length) = line.to_ascii_lowercase().strip_prefix("content-length:")
+                    {
+                        content_length = length.trim().parse().unwrap();
+                    }
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                    request += &line;
+                }
+                let mut payload = vec![0; content_length];
+                std::io::Read::read_exact(&mut reader, &mut payload).unwrap();
+                request += &String::from_utf8(payload).unwrap();
+                seen.lock().unwrap().push(request);
+                write!(
+                    stream,
+                    "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+        (base_url, requests)
+    }
+
+    fn args(base_url: &str) -> ClientArgs {
+        ClientArgs {
+            base_url: base_url.to_owned(),
+            session: Some("abc123".to_owned()),
+            session_file: None,
+        }
+    }
+
+    #[test]
+    fn downloads_once_then_uses_cache() {
+        let (base_url, requests) = serve(200, "1abc2\n");
+        let root = tempfile::tempdir().unwrap();
+        let path = input_path(root.path(), 1);
+
+        assert!(fetch_cached(&args(&base_url), 1, &path).unwrap());
+        assert_eq!(input::read_to_string(&path).unwrap(), "1abc2\n");
+        assert!(!fetch_cached(&args(&base_url), 1, &path).unwrap());
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /2023/day/1/input "));
+        assert!(requests[0].contains("session=abc123"));
+    }
+
+    #[test]
+    fn error_status_is_not_cached() {
+        let (base_url, _) = serve(400, "Please log in");
+        let root = tempfile::tempdir().unwrap();
+        let path = input_path(root.path(), 2);
+
+        let error = fetch_cached(&args(&base_url), 2, &path).unwrap_err();
+        assert!(error.to_string().contains("Please log in"));
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,23 @@
+use aoc_common::{Error, Solver};
+
+pub static DAYS: &[&dyn Solver] = &[
+    &d01::Day01::PUZZLE,
+    &d02::Day02,
+    &d03::Day03,
+    &d04::Day04,
+    &d05::Day05,
+    &d06::Day06,
+    &d07::Day07,
+];
+
+pub fn find(day: u8) -> Option<&'static dyn Solver> {
+    DAYS.iter().copied().find(|solver| solver.day() == day)
+}
+
+/// The requested day, or every registered day when none was given.
+pub fn select(day: Option<u8>) -> Result<Vec<&'static dyn Solver>, Error> {
+    match day {
+        Some(day) => Ok(vec![find(day).ok_or(Error::UnknownDay(day))?]),
+        None => Ok(DAYS.to_vec()),
+    }
+}
@@ -0,0 +1,102 @@
+use std::fs;
+use std::path::Path;
+
+use aoc_common::crypt::{self, Key};
+use aoc_common::{input, Error};
+
+fn read(path: &Path) -> Result<Vec<u8>, Error> {
+    fs::read(path).map_err(|source| Error::Io {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+fn write(path: &Path, contents: &[u8]) -> Result<(), Error> {
+    fs::write(path, contents).map_err(|source| Error::Write {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+fn remove(path: &Path) -> Result<(), Error> {
+    fs::remove_file(path).map_err(|source| Error::Write {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+/// Replaces the plain-text input at `path` with its encrypted copy. Returns
+/// false if there is no plain text to encrypt.
+pub fn encrypt(key: &Key, path: &Path) -> Result<bool, Error> {
+    if !path.exists() {
+        return Ok(false);
+    }
+    let contents = read(path)?;
+    if crypt::is_encrypted(&contents) {
+        return Ok(false);
+    }
+    write(&crypt::encrypted_path(path), &key.encrypt(&contents))?;
+    remove(path)?;
+    Ok(true)
+}
+
+/// Replaces the encrypted copy of the input at `path` with its plain text.
+/// Returns false if there is no encrypted copy.
+pub fn decrypt(key: &Key, path: &Path) -> Result<bool, Error> {
+    let encrypted = crypt::encrypted_path(path);
+    if !encrypted.exists() {
+        return Ok(false);
+    }
+    let plain = key
+        .decrypt(&read(&encrypted)?)
+        .ok_or_else(|| Error::Decrypt(encrypted.clone()))?;
+    write(path, &plain)?;
+    remove(&encrypted)?;
+    Ok(true)
+}
+
+/// Writes a new random key to `path`, refusing to replace an existing one
+/// since that would lock away every input encrypted with it.
+pub fn keygen(path: &Path) -> Result<(), Error> {
+    if path.exists() {
+        return Err(Error::Invalid(format!("{} already exists", path.display())));
+    }
+    input::write_file(path, &format!("{}\n", Key::generate().to_hex()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_then_decrypt_restores_the_input() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("part_1.txt");
+        let encrypted = crypt::encrypted_path(&path);
+        fs::write(&path, "1abc2\n").unwrap();
+        let key = Key::generate();
+
+        assert!(encrypt(&key, &path).unwrap());
+        assert!(!path.exists());
+        assert!(!encrypt(&key, &path).unwrap());
+
+        assert!(matches!(
+            decrypt(&Key::generate(), &path),
+            Err(Error::Decrypt(_))
+        ));
+        assert!(decrypt(&key, &path).unwrap());
+        assert!(!encrypted.exists());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1abc2\n");
+    }
+
+    #[test]
+    fn keygen_keeps_an_existing_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".input-key");
+        keygen(&path).unwrap();
+        let key = fs::read_to_string(&path).unwrap();
+        assert!(Key::from_hex(&key).is_ok());
+        assert!(keygen(&path).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), key);
+    }
+}
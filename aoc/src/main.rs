@@ -0,0 +1,461 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use aoc_common::cli::InputArgs;
+use aoc_common::crypt::{self, Key};
+use aoc_common::input::Source;
+use aoc_common::logging::{self, Verbosity};
+use aoc_common::output::{self, Format};
+use aoc_common::{input, Error, InputKind, Part};
+use clap::{Args, Parser, Subcommand};
+use client::ClientArgs;
+
+mod batch;
+mod bench;
+mod client;
+mod days;
+mod inputs;
+mod report;
+mod scaffold;
+mod stress;
+mod submit;
+mod timing;
+mod verify;
+mod watch;
+
+#[derive(Parser)]
+#[command(about = "Runs the Advent of Code 2023 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    #[command(flatten)]
+    verbosity: Verbosity,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one day, or every registered day when --day is left out
+    Run {
+        #[arg(long)]
+        day: Option<u8>,
+        #[arg(long)]
+        part: Option<Part>,
+        #[command(flatten)]
+        input: InputArgs,
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// Solve one day for every input file in a directory, in parallel
+    Batch {
+        #[arg(long)]
+        day: u8,
+        #[arg(long)]
+        part: Option<Part>,
+        /// Directory holding the inputs, e.g. one file per team member
+        dir: PathBuf,
+    },
+    /// Check the solvers against the answers recorded in each dNN/answers.txt
+    Verify {
+        #[arg(long)]
+        day: Option<u8>,
+        /// Only check the bundled examples, skipping the slow real inputs
+        #[arg(long)]
+        examples_only: bool,
+    },
+    /// Rebuild and rerun a day whenever its source or input changes
+    Watch {
+        #[arg(long)]
+        day: u8,
+        #[arg(long)]
+        part: Option<Part>,
+        #[command(flatten)]
+        input: InputArgs,
+        /// How often to look for changes, in milliseconds
+        #[arg(long, default_value_t = 500)]
+        interval: u64,
+    },
+    /// Solve every day and write an HTML page of the answers, their check
+    /// against answers.txt and the timings, with the history of past reports
+    Report {
+        /// Where to write the page, report/index.html by default
+        #[arg(long)]
+        output: Option<PathBuf>,
+        /// Timings of earlier reports, report/history.txt by default
+        #[arg(long)]
+        history: Option<PathBuf>,
+        /// Only run the bundled examples, skipping the slow real inputs
+        #[arg(long)]
+        examples_only: bool,
+    },
+    /// Time the parse and both parts of each day over repeated runs
+    Bench(BenchArgs),
+    /// Print a random puzzle input for a day, the same one for a given seed
+    Generate {
+        #[arg(long)]
+        day: u8,
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Lines, games, grid side, ... depending on the day
+        #[arg(long, default_value_t = 100)]
+        size: usize,
+    },
+    /// Solve many generated inputs and report the seeds that fail or panic
+    Stress {
+        #[arg(long)]
+        day: Option<u8>,
+        /// First seed to generate from
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Number of inputs to generate per day
+        #[arg(long, default_value_t = 20)]
+        count: u64,
+        #[arg(long, default_value_t = 100)]
+        size: usize,
+    },
+    /// Create the dNN crate for a new day and register it with the runner
+    New {
+        #[arg(long)]
+        day: u8,
+    },
+    /// Download a day's puzzle input into dNN/part_1.txt unless already there
+    Fetch {
+        #[arg(long)]
+        day: u8,
+        #[command(flatten)]
+        client: ClientArgs,
+    },
+    /// Replace each day's part_1.txt with an encrypted part_1.txt.enc, using
+    /// the key from AOC_INPUT_KEY or .input-key
+    Encrypt {
+        #[arg(long)]
+        day: Option<u8>,
+    },
+    /// Turn each day's part_1.txt.enc back into plain text
+    Decrypt {
+        #[arg(long)]
+        day: Option<u8>,
+    },
+    /// Create a random .input-key for encrypting the inputs
+    Keygen,
+    /// Send an answer to the puzzle site, computing it from the real input
+    /// when --answer is left out
+    Submit {
+        #[arg(long)]
+        day: u8,
+        #[arg(long)]
+        part: Part,
+        #[arg(long)]
+        answer: Option<String>,
+        #[command(flatten)]
+        client: ClientArgs,
+    },
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    #[arg(long)]
+    day: Option<u8>,
+    #[command(flatten)]
+    input: InputArgs,
+    /// Number of timed runs per day
+    #[arg(long, default_value_t = 10)]
+    runs: usize,
+    /// Write the timings to this file for later comparison
+    #[arg(long)]
+    save: Option<PathBuf>,
+    /// Compare against timings written by an earlier --save
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+    /// Slowdown of the median, in percent, that counts as a regression
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+}
+
+fn run(
+    day: Option<u8>,
+    part: Option<Part>,
+    input_args: InputArgs,
+    format: Format,
+) -> Result<bool, Error> {
+    if day.is_none() && input_args.input.is_some() {
+        return Err(Error::InputNeedsDay);
+    }
+    let solvers = days::select(day)?;
+    let parts = Part::select(part);
+
+    let mut solved = true;
+    for solver in solvers {
+        let source = match input_args.source(solver, part) {
+            // Running every day with --example skips the days without one
+            Err(Error::NoExample(_)) if day.is_none() => continue,
+            source => source?,
+        };
+        let mut lines = input::stream(&source)?;
+        let run = solver
+            .run_lines(&mut lines, &parts)
+            .map_err(|e| e.in_file(&source))?
+            .in_file(&source);
+        output::print_run(&run, &source, format);
+        solved &= run.solved();
+    }
+    Ok(solved)
+}
+
+fn batch(day: u8, part: Option<Part>, dir: PathBuf) -> Result<bool, Error> {
+    let solver = days::find(day).ok_or(Error::UnknownDay(day))?;
+    let files = batch::input_files(&dir)?;
+    if files.is_empty() {
+        return Err(Error::Invalid(format!(
+            "no input files in {}",
+            dir.display()
+        )));
+    }
+    let parts = Part::select(part);
+    let entries = batch::run_all(solver, &files, &parts);
+    Ok(batch::print_table(&entries, &parts))
+}
+
+fn verify(day: Option<u8>, examples_only: bool) -> Result<bool, Error> {
+    let solvers = days::select(day)?;
+    let kinds: &[InputKind] = if examples_only {
+        &[InputKind::Example]
+    } else {
+        &InputKind::ALL
+    };
+
+    let mut checks = Vec::new();
+    for solver in solvers {
+        checks.extend(verify::check_day(solver, kinds)?);
+    }
+    verify::print_table(&checks);
+    Ok(checks.iter().all(|check| check.passed()))
+}
+
+fn watch(day: u8, part: Option<Part>, input_args: InputArgs, interval: u64) -> Result<bool, Error> {
+    let solver = days::find(day).ok_or(Error::UnknownDay(day))?;
+    let Source::File(path) = input_args.source(solver, part)? else {
+        return Err(Error::Invalid(
+            "watch needs an input file, not stdin".to_owned(),
+        ));
+    };
+    watch::watch(
+        &scaffold::repo_root(),
+        solver,
+        &Part::select(part),
+        &path,
+        Duration::from_millis(interval),
+    )
+}
+
+fn report(
+    output: Option<PathBuf>,
+    history: Option<PathBuf>,
+    examples_only: bool,
+) -> Result<bool, Error> {
+    let dir = report::default_dir(&scaffold::repo_root());
+    let output = output.unwrap_or_else(|| dir.join("index.html"));
+    let history_path = history.unwrap_or_else(|| dir.join("history.txt"));
+    let kinds: &[InputKind] = if examples_only {
+        &[InputKind::Example]
+    } else {
+        &InputKind::ALL
+    };
+
+    let entries = report::collect(days::DAYS, kinds)?;
+    let mut history = report::load_history(&history_path)?;
+    let now = timing::now();
+    report::record(&history_path, &mut history, &entries, now)?;
+    input::write_file(&output, &report::render(&entries, &history, now))?;
+
+    let failed = entries
+        .iter()
+        .filter(|entry| matches!(entry.status(), report::Status::Fail | report::Status::Error))
+        .count();
+    println!(
+        "wrote {} ({} of {} parts failed)",
+        output.display(),
+        failed,
+        entries.len()
+    );
+    Ok(failed == 0)
+}
+
+fn bench(args: BenchArgs) -> Result<bool, Error> {
+    if args.day.is_none() && args.input.input.is_some() {
+        return Err(Error::InputNeedsDay);
+    }
+    let baseline = args.baseline.map(bench::load_baseline).transpose()?;
+
+    let mut benches = Vec::new();
+    for solver in days::select(args.day)? {
+        let source = match args.input.source(solver, None) {
+            Err(Error::NoExample(_)) if args.day.is_none() => continue,
+            source => source?,
+        };
+        let file = input::read_source(&source)?;
+        let timings = bench::bench_day(solver, &file, args.runs).map_err(|e| e.in_file(&source))?;
+        benches.extend(timings);
+    }
+
+    let regressions = bench::print_table(&benches, baseline.as_ref(), args.threshold);
+    if let Some(path) = args.save {
+        bench::save(path, &benches)?;
+    }
+    Ok(regressions == 0)
+}
+
+fn generate(day: u8, seed: u64, size: usize) -> Result<bool, Error> {
+    let solver = days::find(day).ok_or(Error::UnknownDay(day))?;
+    println!("{}", solver.generate(seed, size)?);
+    Ok(true)
+}
+
+fn stress(day: Option<u8>, seed: u64, count: u64, size: usize) -> Result<bool, Error> {
+    let mut outcomes = Vec::new();
+    for solver in days::select(day)? {
+        match stress::stress_day(solver, seed..seed + count, size) {
+            // Stressing every day skips the days without a generator
+            Err(Error::NoGenerator(_)) if day.is_none() => continue,
+            outcome => outcomes.push(outcome?),
+        }
+    }
+    stress::print_report(&outcomes);
+    Ok(outcomes.iter().all(|outcome| outcome.failures.is_empty()))
+}
+
+fn fetch(day: u8, client: &ClientArgs) -> Result<bool, Error> {
+    let path = client::input_path(&scaffold::repo_root(), day);
+    if client::fetch_cached(client, day, &path)? {
+        println!("downloaded {}", path.display());
+    } else {
+        println!("using cached {}", path.display());
+    }
+    Ok(true)
+}
+
+fn crypt(day: Option<u8>, encrypt: bool) -> Result<bool, Error> {
+    let key = Key::load()?;
+    let verb = if encrypt { "encrypted" } else { "decrypted" };
+    let mut changed = 0;
+    for solver in days::select(day)? {
+        let path = solver.input_path();
+        let done = if encrypt {
+            inputs::encrypt(&key, &path)?
+        } else {
+            inputs::decrypt(&key, &path)?
+        };
+        if done {
+            println!("{} {}", verb, path.display());
+            changed += 1;
+        }
+    }
+    println!("{} {} inputs", verb, changed);
+    Ok(true)
+}
+
+fn submit(day: u8, part: Part, answer: Option<String>, client: &ClientArgs) -> Result<bool, Error> {
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let solver = days::find(day).ok_or(Error::UnknownDay(day))?;
+            let path = solver.input_path();
+            let file = input::read_to_string(&path)?;
+            let run = solver
+                .run(&file, &[part])
+                .map_err(|e| e.in_file(path.display()))?;
+            let answer = run.parts.into_iter().next().map(|part| part.answer);
+            answer.expect("one part was run")?.to_string()
+        }
+    };
+
+    let path = submit::submissions_path(&scaffold::repo_root(), day);
+    let response = submit::submit(client, &path, day, part, &answer)?;
+    match response {
+        submit::Response::Judged { verdict, cooldown } => {
+            println!("day {} part {}: {} is {}", day, part, answer, verdict);
+            if let Some(cooldown) = cooldown {
+                println!("next answer allowed in {}s", cooldown.as_secs());
+            }
+            Ok(verdict == submit::Verdict::Correct)
+        }
+        submit::Response::RateLimited(wait) => {
+            println!(
+                "rate limited, wait {}s before submitting again",
+                wait.as_secs()
+            );
+            Ok(false)
+        }
+        submit::Response::WrongLevel => {
+            println!("part {} is already solved or not unlocked yet", part);
+            Ok(false)
+        }
+        submit::Response::Unknown(text) => {
+            println!("unrecognised response: {}", text);
+            Ok(false)
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    logging::init(cli.verbosity);
+    let result = match cli.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            format,
+        } => run(day, part, input, format),
+        Command::Batch { day, part, dir } => batch(day, part, dir),
+        Command::Verify { day, examples_only } => verify(day, examples_only),
+        Command::Watch {
+            day,
+            part,
+            input,
+            interval,
+        } => watch(day, part, input, interval),
+        Command::Report {
+            output,
+            history,
+            examples_only,
+        } => report(output, history, examples_only),
+        Command::Bench(args) => bench(args),
+        Command::Generate { day, seed, size } => generate(day, seed, size),
+        Command::Stress {
+            day,
+            seed,
+            count,
+            size,
+        } => stress(day, seed, count, size),
+        Command::New { day } => scaffold::new_day(&scaffold::repo_root(), day).map(|dir| {
+            println!("created {}", dir.display());
+            true
+        }),
+        Command::Fetch { day, client } => fetch(day, &client),
+        Command::Encrypt { day } => crypt(day, true),
+        Command::Decrypt { day } => crypt(day, false),
+        Command::Keygen => {
+            let path = crypt::key_file();
+            inputs::keygen(&path).map(|()| {
+                println!("wrote {}, keep it out of the repository", path.display());
+                true
+            })
+        }
+        Command::Submit {
+            day,
+            part,
+            answer,
+            client,
+        } => submit(day, part, answer, &client),
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,354 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use aoc_common::{input, Answers, Error, InputKind, ParseError, Part, Solver};
+
+use crate::timing::millis;
+
+/// One part of one input of one day, as shown in the report.
+pub struct Entry {
+    pub day: u8,
+    pub kind: InputKind,
+    pub part: Part,
+    pub expected: Option<String>,
+    /// The answer, or the error that stopped the part or its input; `None`
+    /// when the day has no solution for the part yet.
+    pub got: Option<Result<String, String>>,
+    pub elapsed: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Unverified,
+    Unsolved,
+    Error,
+}
+
+impl Entry {
+    pub fn status(&self) -> Status {
+        match (&self.got, &self.expected) {
+            (None, _) => Status::Unsolved,
+            (Some(Err(_)), _) => Status::Error,
+            (Some(Ok(_)), None) => Status::Unverified,
+            (Some(Ok(got)), Some(expected)) if got == expected => Status::Pass,
+            (Some(Ok(_)), Some(_)) => Status::Fail,
+        }
+    }
+}
+
+/// Runs both parts of every solver on each kind of input it has, against
+/// the answers recorded for it.
+pub fn collect(solvers: &[&dyn Solver], kinds: &[InputKind]) -> Result<Vec<Entry>, Error> {
+    let mut entries = Vec::new();
+    for &solver in solvers {
+        let answers = Answers::load(solver.answers_path())?;
+        for &kind in kinds {
+            for part in Part::ALL {
+                let path = match kind {
+                    InputKind::Example => match solver.example_path(part) {
+                        Some(path) => path,
+                        None => continue,
+                    },
+                    InputKind::Real => solver.input_path(),
+                };
+                // Parts run one at a time, as the examples of the two may differ
+                let run = input::read_to_string(&path)
+                    .and_then(|file| solver.run(&file, &[part]))
+                    .map_err(|e| e.in_file(path.display()).to_string());
+                let (got, elapsed) = match &run {
+                    Ok(run) => {
+                        let part_run = &run.parts[0];
+                        let got = match &part_run.answer {
+                            Ok(answer) => Some(Ok(answer.to_string())),
+                            Err(Error::Unsolved { .. }) => None,
+                            Err(e) => Some(Err(e.to_string())),
+                        };
+                        (got, part_run.answer.is_ok().then_some(part_run.elapsed))
+                    }
+                    Err(e) => (Some(Err(e.clone())), None),
+                };
+                entries.push(Entry {
+                    day: solver.day(),
+                    kind,
+                    part,
+                    expected: answers.get(kind, part).map(str::to_owned),
+                    got,
+                    elapsed,
+                });
+            }
+        }
+    }
+    Ok(entries)
+}
+
+/// Past timings of each (day, input, part), oldest first, as
+/// `(unix seconds, time taken)`.
+pub type History = BTreeMap<(u8, InputKind, Part), Vec<(u64, Duration)>>;
+
+pub fn default_dir(root: &Path) -> PathBuf {
+    root.join("report")
+}
+
+/// Reads the history file; a missing file is an empty history.
+pub fn load_history(path: &Path) -> Result<History, Error> {
+    let mut history = History::new();
+    if !path.exists() {
+        return Ok(history);
+    }
+    let file = input::read_to_string(path)?;
+    for (i, line) in file.lines().enumerate() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.is_empty() || fields[0].starts_with('#') {
+            continue;
+        }
+        let invalid = || {
+            Error::from(
+                ParseError::at(line, line, "expected `<unix> <day> <input> <part> <ns>`")
+                    .on_line(i + 1)
+                    .in_file(path.display()),
+            )
+        };
+        let [time, day, kind, part, nanos] = fields[..] else {
+            return Err(invalid());
+        };
+        let kind = kind.parse::<InputKind>().map_err(|_| invalid())?;
+        let time = time.parse::<u64>().map_err(|_| invalid())?;
+        let day = day.parse::<u8>().map_err(|_| invalid())?;
+        let part = part.parse::<Part>().map_err(|_| invalid())?;
+        let nanos = nanos.parse::<u64>().map_err(|_| invalid())?;
+        history
+            .entry((day, kind, part))
+            .or_default()
+            .push((time, Duration::from_nanos(nanos)));
+    }
+    Ok(history)
+}
+
+/// Adds this run's timings to the history, both in memory and on disk.
+pub fn record(
+    path: &Path,
+    history: &mut History,
+    entries: &[Entry],
+    time: u64,
+) -> Result<(), Error> {
+    for entry in entries {
+        if let Some(elapsed) = entry.elapsed {
+            history
+                .entry((entry.day, entry.kind, entry.part))
+                .or_default()
+                .push((time, elapsed));
+        }
+    }
+    let mut file = String::from("# unix day input part elapsed_ns\n");
+    for ((day, kind, part), runs) in history.iter() {
+        for (time, elapsed) in runs {
+            writeln!(
+                file,
+                "{} {} {} {} {}",
+                time,
+                day,
+                kind,
+                part,
+                elapsed.as_nanos()
+            )
+            .expect("writing to a String cannot fail");
+        }
+    }
+    input::write_file(path, &file)
+}
+
+/// `YYYY-MM-DD HH:MM` in UTC.
+fn format_time(unix: u64) -> String {
+    // Civil-from-days, after Howard Hinnant's date algorithms
+    let days = (unix / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    let seconds = unix % 86_400;
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// A small inline SVG line of the timings, scaled to the slowest run.
+fn sparkline(runs: &[(u64, Duration)]) -> String {
+    const WIDTH: f64 = 160.0;
+    const HEIGHT: f64 = 32.0;
+    if runs.len() < 2 {
+        return String::new();
+    }
+    let slowest = runs
+        .iter()
+        .map(|(_, elapsed)| elapsed.as_secs_f64())
+        .fold(f64::EPSILON, f64::max);
+    let step = WIDTH / (runs.len() - 1) as f64;
+    let points: Vec<String> = runs
+        .iter()
+        .enumerate()
+        .map(|(i, (_, elapsed))| {
+            let y = HEIGHT - elapsed.as_secs_f64() / slowest * (HEIGHT - 2.0) - 1.0;
+            format!("{:.1},{:.1}", i as f64 * step, y)
+        })
+        .collect();
+    let title: Vec<String> = runs
+        .iter()
+        .map(|(time, elapsed)| format!("{}: {} ms", format_time(*time), millis(*elapsed)))
+        .collect();
+    format!(
+        "<svg width=\"{}\" height=\"{}\"><title>{}</title>\
+         <polyline fill=\"none\" stroke=\"#36c\" stroke-width=\"1.5\" points=\"{}\"/></svg>",
+        WIDTH,
+        HEIGHT,
+        escape(&title.join("\n")),
+        points.join(" ")
+    )
+}
+
+/// The whole report as a single page with no outside resources.
+pub fn render(entries: &[Entry], history: &History, generated: u64) -> String {
+    let count = |status| entries.iter().filter(|e| e.status() == status).count();
+    let mut html = String::new();
+    let mut out = |text: String| html.push_str(&text);
+
+    out(format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">\
+         <title>Advent of Code 2023</title>\n<style>\
+         body{{font-family:sans-serif;margin:2em}}\
+         table{{border-collapse:collapse}}\
+         th,td{{padding:.3em .8em;border-bottom:1px solid #ddd;text-align:left}}\
+         td.num{{text-align:right;font-variant-numeric:tabular-nums}}\
+         .pass{{color:#080}}.fail,.error{{color:#c00}}.unverified,.unsolved{{color:#888}}\
+         </style></head><body>\n<h1>Advent of Code 2023</h1>\n\
+         <p>Generated {} UTC: {} passed, {} failed, {} errors, {} unverified, \
+         {} unsolved.</p>\n",
+        format_time(generated),
+        count(Status::Pass),
+        count(Status::Fail),
+        count(Status::Error),
+        count(Status::Unverified),
+        count(Status::Unsolved)
+    ));
+    out(
+        "<table>\n<tr><th>Day</th><th>Input</th><th>Part</th><th>Answer</th>\
+         <th>Expected</th><th>Status</th><th>Time (ms)</th><th>History</th></tr>\n"
+            .to_owned(),
+    );
+    for entry in entries {
+        let (class, status) = match entry.status() {
+            Status::Pass => ("pass", "pass"),
+            Status::Fail => ("fail", "FAIL"),
+            Status::Error => ("error", "error"),
+            Status::Unverified => ("unverified", "unverified"),
+            Status::Unsolved => ("unsolved", "unsolved"),
+        };
+        let got = match &entry.got {
+            None => String::new(),
+            Some(Ok(answer)) => escape(answer),
+            Some(Err(e)) => format!("<span class=\"error\">{}</span>", escape(e)),
+        };
+        let runs = history
+            .get(&(entry.day, entry.kind, entry.part))
+            .map_or(&[][..], Vec::as_slice);
+        out(format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td>\
+             <td class=\"{}\">{}</td><td class=\"num\">{}</td><td>{}</td></tr>\n",
+            entry.day,
+            entry.kind,
+            entry.part,
+            got,
+            entry.expected.as_deref().map_or(String::new(), escape),
+            class,
+            status,
+            entry.elapsed.map_or(String::new(), millis),
+            sparkline(runs)
+        ));
+    }
+    out("</table>\n</body></html>\n".to_owned());
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(part: Part, got: Option<Result<&str, &str>>, expected: Option<&str>) -> Entry {
+        Entry {
+            day: 6,
+            kind: InputKind::Example,
+            part,
+            expected: expected.map(str::to_owned),
+            got: got.map(|got| got.map(str::to_owned).map_err(str::to_owned)),
+            elapsed: matches!(got, Some(Ok(_))).then(|| Duration::from_micros(1500)),
+        }
+    }
+
+    #[test]
+    fn statuses() {
+        assert_eq!(
+            entry(Part::One, Some(Ok("288")), Some("288")).status(),
+            Status::Pass
+        );
+        assert_eq!(
+            entry(Part::One, Some(Ok("1")), Some("288")).status(),
+            Status::Fail
+        );
+        assert_eq!(
+            entry(Part::One, Some(Ok("1")), None).status(),
+            Status::Unverified
+        );
+        assert_eq!(entry(Part::Two, None, None).status(), Status::Unsolved);
+        assert_eq!(
+            entry(Part::Two, Some(Err("boom")), Some("2")).status(),
+            Status::Error
+        );
+    }
+
+    #[test]
+    fn history_round_trips_and_renders() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.txt");
+        let entries = [
+            entry(Part::One, Some(Ok("288")), Some("288")),
+            entry(Part::Two, Some(Err("<oops>")), None),
+        ];
+
+        let mut history = load_history(&path).unwrap();
+        record(&path, &mut history, &entries, 1_700_000_000).unwrap();
+        record(&path, &mut history, &entries, 1_700_086_400).unwrap();
+        let loaded = load_history(&path).unwrap();
+        assert_eq!(loaded, history);
+        assert_eq!(loaded[&(6, InputKind::Example, Part::One)].len(), 2);
+        assert!(!loaded.contains_key(&(6, InputKind::Example, Part::Two)));
+
+        let html = render(&entries, &loaded, 1_700_086_400);
+        assert!(html.contains("Generated 2023-11-15 22:13 UTC: 1 passed"));
+        assert!(html.contains("&lt;oops&gt;"));
+        assert!(html.contains("<polyline"));
+    }
+}
@@ -0,0 +1,180 @@
+use std::path::{Path, PathBuf};
+
+use aoc_common::{input, Error};
+
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml.tmpl");
+const LIB_RS: &str = include_str!("../templates/lib.rs.tmpl");
+const MAIN_RS: &str = include_str!("../templates/main.rs.tmpl");
+
+/// The repository root, one level above this crate.
+pub fn repo_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the runner crate lives inside the workspace")
+        .to_owned()
+}
+
+fn render(template: &str, day: u8) -> String {
+    template
+        .replace("{{dd}}", &format!("{:02}", day))
+        .replace("{{day}}", &day.to_string())
+}
+
+/// Creates `dNN/` under `root` and registers it with the workspace and the
+/// runner. Returns the new crate's directory.
+pub fn new_day(root: &Path, day: u8) -> Result<PathBuf, Error> {
+    if !(1..=25).contains(&day) {
+        return Err(Error::Invalid(format!("day {} is outside 1..=25", day)));
+    }
+    let name = format!("d{:02}", day);
+    let dir = root.join(&name);
+    if dir.exists() {
+        return Err(Error::Invalid(format!("{} already exists", dir.display())));
+    }
+
+    // Edit the registries first so a failure leaves no half-made crate behind
+    let workspace = root.join("Cargo.toml");
+    let runner_manifest = root.join("aoc/Cargo.toml");
+    let registry = root.join("aoc/src/days.rs");
+    let edits = [
+        (
+            &workspace,
+            insert_before_last(
+                &input::read_to_string(&workspace)?,
+                "]",
+                &format!("    \"{}\",\n", name),
+            ),
+        ),
+        (
+            &runner_manifest,
+            insert_after_last(
+                &input::read_to_string(&runner_manifest)?,
+                "= { path = \"../d",
+                &format!("{} = {{ path = \"../{}\" }}\n", name, name),
+            ),
+        ),
+        (
+            &registry,
+            insert_before_last(
+                &input::read_to_string(&registry)?,
+                "];",
+                &format!("    &{}::Day{:02},\n", name, day),
+            ),
+        ),
+    ];
+    let edits: Vec<(&PathBuf, String)> = edits
+        .into_iter()
+        .map(|(path, edited)| {
+            edited.map(|edited| (path, edited)).ok_or_else(|| {
+                Error::Invalid(format!(
+                    "cannot find where to register {} in {}",
+                    name,
+                    path.display()
+                ))
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    input::write_file(dir.join("Cargo.toml"), &render(CARGO_TOML, day))?;
+    input::write_file(dir.join("src/lib.rs"), &render(LIB_RS, day))?;
+    input::write_file(dir.join("src/main.rs"), &render(MAIN_RS, day))?;
+    input::write_file(dir.join("test.txt"), "")?;
+    input::write_file(dir.join("part_1.txt"), "")?;
+    input::write_file(dir.join("answers.txt"), "# input part answer\n")?;
+    for (path, edited) in edits {
+        input::write_file(path, &edited)?;
+    }
+    Ok(dir)
+}
+
+/// Inserts `text` at the start of the line holding the last `marker`.
+fn insert_before_last(file: &str, marker: &str, text: &str) -> Option<String> {
+    let at = file.rfind(marker)?;
+    let line_start = file[..at].rfind('\n').map_or(0, |i| i + 1);
+    Some(format!(
+        "{}{}{}",
+        &file[..line_start],
+        text,
+        &file[line_start..]
+    ))
+}
+
+/// Inserts `text` as a new line after the line holding the last `marker`.
+fn insert_after_last(file: &str, marker: &str, text: &str) -> Option<String> {
+    let at = file.rfind(marker)?;
+    let line_end = file[at..].find('\n').map_or(file.len(), |i| at + i + 1);
+    let separator = if file[..line_end].ends_with('\n') {
+        ""
+    } else {
+        "\n"
+    };
+    Some(format!(
+        "{}{}{}{}",
+        &file[..line_end],
+        separator,
+        text,
+        &file[line_end..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    /// A copy of the workspace's registries, with no day crates.
+    fn workspace() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("aoc/src")).unwrap();
+        for file in ["Cargo.toml", "aoc/Cargo.toml", "aoc/src/days.rs"] {
+            fs::copy(repo_root().join(file), dir.path().join(file)).unwrap();
+        }
+        dir
+    }
+
+    fn read(path: PathBuf) -> String {
+        input::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn new_day_creates_and_registers_the_crate() {
+        let root = workspace();
+        let root = root.path();
+        let dir = new_day(root, 8).unwrap();
+        assert_eq!(dir, root.join("d08"));
+
+        assert!(read(dir.join("Cargo.toml")).contains("name = \"d08\""));
+        let lib = read(dir.join("src/lib.rs"));
+        assert!(lib.contains("pub struct Day08;"));
+        assert!(lib.contains("const DAY: u8 = 8;"));
+        assert!(read(dir.join("src/main.rs")).contains("Day08"));
+        assert_eq!(read(dir.join("test.txt")), "");
+        assert_eq!(read(dir.join("part_1.txt")), "");
+        assert_eq!(read(dir.join("answers.txt")), "# input part answer\n");
+
+        assert!(read(root.join("Cargo.toml")).contains("    \"d07\",\n    \"d08\",\n]"));
+        assert!(read(root.join("aoc/Cargo.toml"))
+            .contains("d07 = { path = \"../d07\" }\nd08 = { path = \"../d08\" }\n"));
+        assert!(
+            read(root.join("aoc/src/days.rs")).contains("    &d07::Day07,\n    &d08::Day08,\n];")
+        );
+    }
+
+    #[test]
+    fn new_day_refuses_to_overwrite() {
+        let root = workspace();
+        let root = root.path();
+        new_day(root, 8).unwrap();
+        let registry = read(root.join("aoc/src/days.rs"));
+        input::write_file(root.join("d08/src/lib.rs"), "// started").unwrap();
+
+        let error = new_day(root, 8).unwrap_err().to_string();
+        assert!(error.ends_with("d08 already exists"), "{}", error);
+        assert_eq!(read(root.join("d08/src/lib.rs")), "// started");
+        assert_eq!(read(root.join("aoc/src/days.rs")), registry);
+
+        assert!(new_day(root, 26).is_err());
+        assert!(!root.join("d26").exists());
+    }
+}
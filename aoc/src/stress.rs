@@ -0,0 +1,86 @@
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+
+use aoc_common::{Error, Part, Solver};
+
+pub struct Failure {
+    pub seed: u64,
+    pub message: String,
+}
+
+pub struct Outcome {
+    pub day: u8,
+    pub inputs: usize,
+    pub size: usize,
+    pub failures: Vec<Failure>,
+}
+
+/// Generates an input for every seed in `seeds` and solves both parts of it,
+/// collecting the seeds whose parse or parts failed or panicked. A part the
+/// day has not solved yet is not counted as a failure.
+pub fn stress_day(solver: &dyn Solver, seeds: Range<u64>, size: usize) -> Result<Outcome, Error> {
+    let mut outcome = Outcome {
+        day: solver.day(),
+        inputs: 0,
+        size,
+        failures: Vec::new(),
+    };
+    for seed in seeds {
+        let file = solver.generate(seed, size)?;
+        let run = panic::catch_unwind(AssertUnwindSafe(|| solver.run(&file, &Part::ALL)));
+        let message = match run {
+            Err(panic) => Some(panic_message(&*panic)),
+            Ok(Err(e)) => Some(e.to_string()),
+            Ok(Ok(run)) => run.parts.into_iter().find_map(|part| match part.answer {
+                Err(Error::Unsolved { .. }) | Ok(_) => None,
+                Err(e) => Some(format!("part {}: {}", part.part, e)),
+            }),
+        };
+        if let Some(message) = message {
+            outcome.failures.push(Failure { seed, message });
+        }
+        outcome.inputs += 1;
+    }
+    Ok(outcome)
+}
+
+pub fn panic_message(panic: &(dyn std::any::Any + Send)) -> String {
+    let message = panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown cause");
+    format!("panicked: {}", message)
+}
+
+pub fn print_report(outcomes: &[Outcome]) {
+    for outcome in outcomes {
+        println!(
+            "day {:>2}: {} inputs, {} failed",
+            outcome.day,
+            outcome.inputs,
+            outcome.failures.len()
+        );
+        for failure in &outcome.failures {
+            println!("  seed {}: {}", failure.seed, failure.message);
+            println!(
+                "    reproduce with `aoc generate --day {} --seed {} --size {}`",
+                outcome.day, failure.seed, outcome.size
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_inputs_solve() {
+        for solver in [&d02::Day02 as &dyn Solver, &d07::Day07] {
+            let outcome = stress_day(solver, 0..5, 20).unwrap();
+            assert_eq!(outcome.inputs, 5);
+            assert!(outcome.failures.is_empty());
+        }
+    }
+}
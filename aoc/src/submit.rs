@@ -0,0 +1,388 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use aoc_common::{input, Error, ParseError, Part};
+
+use crate::client::ClientArgs;
+use crate::timing::now;
+
+/// What the site made of a judged answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+}
+
+impl Verdict {
+    fn token(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Incorrect => "incorrect",
+        }
+    }
+
+    fn from_token(token: &str) -> Option<Self> {
+        [
+            Verdict::Correct,
+            Verdict::TooHigh,
+            Verdict::TooLow,
+            Verdict::Incorrect,
+        ]
+        .into_iter()
+        .find(|verdict| verdict.token() == token)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => f.write_str("correct"),
+            Verdict::TooHigh => f.write_str("too high"),
+            Verdict::TooLow => f.write_str("too low"),
+            Verdict::Incorrect => f.write_str("incorrect"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Response {
+    Judged {
+        verdict: Verdict,
+        cooldown: Option<Duration>,
+    },
+    /// Submitted too soon after the previous answer; nothing was judged.
+    RateLimited(Duration),
+    /// The part is already solved or not unlocked yet.
+    WrongLevel,
+    Unknown(String),
+}
+
+/// Reads the verdict out of the page returned for a submission.
+pub fn parse_response(page: &str) -> Response {
+    let text = article_text(page);
+    let lower = text.to_lowercase();
+    if lower.contains("you gave an answer too recently") {
+        let wait = between(&lower, "you have ", " left to wait")
+            .and_then(parse_wait)
+            .unwrap_or(Duration::from_secs(60));
+        return Response::RateLimited(wait);
+    }
+    if lower.contains("you don't seem to be solving the right level") {
+        return Response::WrongLevel;
+    }
+
+    let verdict = if lower.contains("that's the right answer") {
+        Verdict::Correct
+    } else if lower.contains("your answer is too high") {
+        Verdict::TooHigh
+    } else if lower.contains("your answer is too low") {
+        Verdict::TooLow
+    } else if lower.contains("that's not the right answer") {
+        Verdict::Incorrect
+    } else {
+        return Response::Unknown(text);
+    };
+    let cooldown = between(&lower, "please wait ", " before trying again").and_then(parse_wait);
+    Response::Judged { verdict, cooldown }
+}
+
+/// The text of the page's `<article>`, or the whole page, without tags.
+fn article_text(page: &str) -> String {
+    let article = between(page, "<article>", "</article>").unwrap_or(page);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn between<'a>(text: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let from = text.find(start)? + start.len();
+    let to = text[from..].find(end)? + from;
+    Some(&text[from..to])
+}
+
+/// Understands both "4m 30s" and "one minute" / "5 minutes".
+fn parse_wait(text: &str) -> Option<Duration> {
+    let words = [
+        ("one", 1),
+        ("two", 2),
+        ("three", 3),
+        ("four", 4),
+        ("five", 5),
+        ("ten", 10),
+    ];
+    let mut seconds = 0;
+    let mut pending: Option<u64> = None;
+    for token in text.split_whitespace() {
+        let token = token.trim_matches(|c: char| !c.is_alphanumeric());
+        let digits: String = token.chars().take_while(|c| c.is_ascii_digit()).collect();
+        let unit = &token[digits.len()..];
+        let value = if digits.is_empty() {
+            pending.or_else(|| words.iter().find(|(w, _)| *w == unit).map(|&(_, n)| n))
+        } else {
+            digits.parse().ok()
+        };
+        let scale = match unit {
+            "h" | "hour" | "hours" => 3600,
+            "m" | "minute" | "minutes" => 60,
+            "s" | "second" | "seconds" => 1,
+            _ => {
+                pending = value;
+                continue;
+            }
+        };
+        seconds += value? * scale;
+        pending = None;
+    }
+    (seconds > 0).then(|| Duration::from_secs(seconds))
+}
+
+/// Answers already sent for one day, and when the next one may be sent.
+#[derive(Debug, Default)]
+pub struct Submissions {
+    guesses: Vec<(Part, String, Verdict)>,
+    cooldown_until: u64,
+}
+
+pub fn submissions_path(root: &Path, day: u8) -> PathBuf {
+    root.join(format!("d{:02}", day)).join("submissions.txt")
+}
+
+impl Submissions {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let mut submissions = Submissions::default();
+        if !path.exists() {
+            return Ok(submissions);
+        }
+        let file = input::read_to_string(path)?;
+        for (i, line) in file.lines().enumerate() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let invalid = || {
+                Error::from(
+                    ParseError::at(line, line, "expected `guess <part> <answer> <verdict>`")
+                        .on_line(i + 1)
+                        .in_file(path.display()),
+                )
+            };
+            match fields[..] {
+                [] => {}
+                [comment, ..] if comment.starts_with('#') => {}
+                ["cooldown", until] => {
+                    submissions.cooldown_until = until.parse().map_err(|_| invalid())?
+                }
+                ["guess", part, answer, verdict] => submissions.guesses.push((
+                    part.parse().map_err(|_| invalid())?,
+                    answer.to_owned(),
+                    Verdict::from_token(verdict).ok_or_else(invalid)?,
+                )),
+                _ => return Err(invalid()),
+            }
+        }
+        Ok(submissions)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let mut file = String::from("# guess <part> <answer> <verdict> | cooldown <unix time>\n");
+        for (part, answer, verdict) in &self.guesses {
+            file += &format!("guess {} {} {}\n", part, answer, verdict.token());
+        }
+        if self.cooldown_until > 0 {
+            file += &format!("cooldown {}\n", self.cooldown_until);
+        }
+        input::write_file(path, &file)
+    }
+
+    /// Why `answer` should not be sent, if what is already known rules it out.
+    pub fn refusal(&self, part: Part, answer: &str) -> Option<String> {
+        // Guesses are kept as whitespace-separated fields
+        if answer.is_empty() || answer.contains(char::is_whitespace) {
+            return Some(format!("{:?} is empty or holds whitespace", answer));
+        }
+        let remaining = self.cooldown_until.saturating_sub(now());
+        if remaining > 0 {
+            return Some(format!("rate limited, wait another {}s", remaining));
+        }
+        let number = answer.parse::<i64>().ok();
+        for (guessed_part, guess, verdict) in &self.guesses {
+            if *guessed_part != part {
+                continue;
+            }
+            if *verdict == Verdict::Correct {
+                return Some(format!("part {} was already solved with {}", part, guess));
+            }
+            if guess == answer {
+                return Some(format!("{} was already submitted: {}", answer, verdict));
+            }
+            let bound = guess.parse::<i64>().ok();
+            match (number, bound, verdict) {
+                (Some(n), Some(b), Verdict::TooHigh) if n >= b => {
+                    return Some(format!("{} is too high, {} already was", answer, guess))
+                }
+                (Some(n), Some(b), Verdict::TooLow) if n <= b => {
+                    return Some(format!("{} is too low, {} already was", answer, guess))
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    pub fn record(&mut self, part: Part, answer: &str, response: &Response) {
+        let cooldown = match response {
+            Response::Judged { verdict, cooldown } => {
+                self.guesses.push((part, answer.to_owned(), *verdict));
+                *cooldown
+            }
+            Response::RateLimited(wait) => Some(*wait),
+            Response::WrongLevel | Response::Unknown(_) => None,
+        };
+        if let Some(cooldown) = cooldown {
+            self.cooldown_until = now() + cooldown.as_secs();
+        }
+    }
+}
+
+/// Submits `answer` unless the recorded guesses already rule it out.
+pub fn submit(
+    client: &ClientArgs,
+    path: &Path,
+    day: u8,
+    part: Part,
+    answer: &str,
+) -> Result<Response, Error> {
+    let mut submissions = Submissions::load(path)?;
+    if let Some(reason) = submissions.refusal(part, answer) {
+        return Err(Error::Invalid(format!("not submitting: {}", reason)));
+    }
+    let page = client.client()?.submit_answer(day, part, answer)?;
+    let response = parse_response(&page);
+    submissions.record(part, answer, &response);
+    submissions.save(path)?;
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::serve;
+
+    const TOO_HIGH: &str = "<main><article><p>That's not the right answer; your answer is too \
+        high.  If you're stuck, make sure you're using the full input data. Please wait one \
+        minute before trying again. <a href=\"/2023/day/1\">[Return to Day 1]</a></p></article></main>";
+    const CORRECT: &str =
+        "<article><p>That's the right answer!  You are <span>one gold star</span> \
+        closer to restoring snow operations.</p></article>";
+    const TOO_RECENT: &str = "<article><p>You gave an answer too recently; you have to wait \
+        after submitting an answer before trying again.  You have 4m 30s left to wait. \
+        <a href=\"/2023/day/1\">[Return to Day 1]</a></p></article>";
+
+    #[test]
+    fn parses_responses() {
+        assert_eq!(
+            parse_response(TOO_HIGH),
+            Response::Judged {
+                verdict: Verdict::TooHigh,
+                cooldown: Some(Duration::from_secs(60))
+            }
+        );
+        assert_eq!(
+            parse_response(CORRECT),
+            Response::Judged {
+                verdict: Verdict::Correct,
+                cooldown: None
+            }
+        );
+        assert_eq!(
+            parse_response(TOO_RECENT),
+            Response::RateLimited(Duration::from_secs(270))
+        );
+        assert_eq!(
+            parse_response(
+                "<article><p>You don't seem to be solving the right level.</p></article>"
+            ),
+            Response::WrongLevel
+        );
+    }
+
+    #[test]
+    fn refuses_known_bad_answers() {
+        let mut submissions = Submissions::default();
+        let judged = |verdict| Response::Judged {
+            verdict,
+            cooldown: None,
+        };
+        submissions.record(Part::One, "500", &judged(Verdict::TooHigh));
+        submissions.record(Part::One, "100", &judged(Verdict::TooLow));
+
+        assert!(submissions.refusal(Part::One, "500").is_some());
+        assert!(submissions.refusal(Part::One, "600").is_some());
+        assert!(submissions.refusal(Part::One, "50").is_some());
+        assert!(submissions.refusal(Part::One, "300").is_none());
+        assert!(submissions.refusal(Part::Two, "500").is_none());
+    }
+
+    #[test]
+    fn refuses_answers_that_cannot_be_recorded() {
+        let (base_url, requests) = serve(200, CORRECT);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("submissions.txt");
+        let client = ClientArgs {
+            base_url,
+            session: Some("abc123".to_owned()),
+            session_file: None,
+        };
+
+        for answer in ["12 34", "1234\n", ""] {
+            let error = submit(&client, &path, 1, Part::One, answer).unwrap_err();
+            assert!(error.to_string().contains("whitespace"), "{}", error);
+        }
+        assert!(requests.lock().unwrap().is_empty());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn submits_and_records_against_mock_server() {
+        let (base_url, requests) = serve(200, TOO_HIGH);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("submissions.txt");
+        let client = ClientArgs {
+            base_url,
+            session: Some("abc123".to_owned()),
+            session_file: None,
+        };
+
+        let response = submit(&client, &path, 1, Part::One, "500").unwrap();
+        assert!(matches!(
+            response,
+            Response::Judged {
+                verdict: Verdict::TooHigh,
+                ..
+            }
+        ));
+        {
+            let requests = requests.lock().unwrap();
+            assert!(requests[0].starts_with("POST /2023/day/1/answer "));
+            assert!(requests[0].ends_with("level=1&answer=500"));
+        }
+
+        // The one minute cooldown from the page is remembered on disk
+        let error = submit(&client, &path, 1, Part::One, "400").unwrap_err();
+        assert!(error.to_string().contains("rate limited"));
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        let submissions = Submissions::load(&path).unwrap();
+        assert!(submissions.refusal(Part::One, "500").is_some());
+    }
+}
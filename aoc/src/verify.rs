@@ -0,0 +1,189 @@
+use aoc_common::{input, Answers, Error, InputKind, Part, Solver};
+
+pub struct Check {
+    pub day: u8,
+    pub kind: InputKind,
+    pub part: Part,
+    pub expected: String,
+    /// The solver's answer, or the error that stopped it from producing one.
+    pub got: Result<String, String>,
+}
+
+impl Check {
+    pub fn passed(&self) -> bool {
+        self.got.as_deref() == Ok(self.expected.as_str())
+    }
+}
+
+/// Runs every recorded answer of `solver` for the given input kinds.
+pub fn check_day(solver: &dyn Solver, kinds: &[InputKind]) -> Result<Vec<Check>, Error> {
+    let answers = Answers::load(solver.answers_path())?;
+    let mut checks = Vec::new();
+
+    for &kind in kinds {
+        for part in Part::ALL {
+            let Some(answer) = answers.get(kind, part) else {
+                continue;
+            };
+            let path = match kind {
+                InputKind::Example => solver
+                    .example_path(part)
+                    .ok_or(Error::NoExample(solver.day()))?,
+                InputKind::Real => solver.input_path(),
+            };
+            // Parts run one at a time, as the examples of the two may differ
+            let run = input::read_to_string(&path)
+                .and_then(|file| solver.run(&file, &[part]))
+                .map_err(|e| e.in_file(path.display()));
+            let got = match &run {
+                Ok(run) => match &run.parts[0].answer {
+                    Ok(answer) => Ok(answer.to_string()),
+                    Err(e) => Err(e.to_string()),
+                },
+                Err(e) => Err(e.to_string()),
+            };
+            checks.push(Check {
+                day: solver.day(),
+                kind,
+                part,
+                expected: answer.to_owned(),
+                got,
+            });
+        }
+    }
+    Ok(checks)
+}
+
+pub fn print_table(checks: &[Check]) {
+    let width = checks
+        .iter()
+        .map(|check| check.expected.len())
+        .max()
+        .unwrap_or(0)
+        .max("expected".len());
+
+    println!(
+        "{:<4} {:<8} {:<5} {:<6} {:<width$} got",
+        "day", "input", "part", "status", "expected"
+    );
+    for check in checks {
+        let status = if check.passed() { "PASS" } else { "FAIL" };
+        let got = match &check.got {
+            Ok(answer) if check.passed() => answer.clone(),
+            Ok(answer) => format!("{} (differs)", answer),
+            Err(e) => format!("error: {}", e),
+        };
+        println!(
+            "{:<4} {:<8} {:<5} {:<6} {:<width$} {}",
+            format!("{:02}", check.day),
+            check.kind,
+            check.part,
+            status,
+            check.expected,
+            got
+        );
+    }
+
+    let failed = checks.iter().filter(|check| !check.passed()).count();
+    println!("\n{} passed, {} failed", checks.len() - failed, failed);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use aoc_common::input::LineStream;
+    use aoc_common::{Run, Solution};
+
+    use super::*;
+
+    /// Day 6 with its answers and inputs in `dir`, so each verdict can be
+    /// set up.
+    struct InDir {
+        dir: PathBuf,
+    }
+
+    impl Solver for InDir {
+        fn day(&self) -> u8 {
+            d06::Day06::DAY
+        }
+
+        fn input_path(&self) -> PathBuf {
+            self.dir.join("part_1.txt")
+        }
+
+        fn example_path(&self, _part: Part) -> Option<PathBuf> {
+            Some(self.dir.join("test.txt"))
+        }
+
+        fn answers_path(&self) -> PathBuf {
+            self.dir.join("answers.txt")
+        }
+
+        fn run(&self, input: &str, parts: &[Part]) -> Result<Run, Error> {
+            d06::Day06.run(input, parts)
+        }
+
+        fn run_lines(&self, lines: &mut LineStream, parts: &[Part]) -> Result<Run, Error> {
+            d06::Day06.run_lines(lines, parts)
+        }
+
+        fn generate(&self, seed: u64, size: usize) -> Result<String, Error> {
+            Solver::generate(&d06::Day06, seed, size)
+        }
+    }
+
+    #[test]
+    fn checks_pass_fail_and_missing_inputs() {
+        let dir = tempfile::tempdir().unwrap();
+        let solver = InDir {
+            dir: dir.path().to_owned(),
+        };
+        fs::write(dir.path().join("test.txt"), "Time: 7\nDistance: 9\n").unwrap();
+        fs::write(
+            dir.path().join("answers.txt"),
+            "# input part answer\nexample 1 4\nexample 2 5\nreal 1 4\n",
+        )
+        .unwrap();
+
+        let checks = check_day(&solver, &InputKind::ALL).unwrap();
+        let verdicts: Vec<(InputKind, Part, bool)> = checks
+            .iter()
+            .map(|check| (check.kind, check.part, check.passed()))
+            .collect();
+        assert_eq!(
+            verdicts,
+            [
+                (InputKind::Example, Part::One, true),
+                (InputKind::Example, Part::Two, false),
+                (InputKind::Real, Part::One, false),
+            ]
+        );
+        assert_eq!(checks[1].got, Ok("4".to_owned()));
+        // The real input was never written
+        let error = checks[2].got.as_ref().unwrap_err();
+        assert!(error.contains("part_1.txt"), "{}", error);
+
+        let checks = check_day(&solver, &[InputKind::Example]).unwrap();
+        assert_eq!(checks.len(), 2);
+    }
+
+    #[test]
+    fn each_part_is_checked_on_its_own_example() {
+        // Day 1 gives a second example for part 2
+        let checks = check_day(&d01::Day01::PUZZLE, &[InputKind::Example]).unwrap();
+        let got: Vec<_> = checks.iter().map(|check| check.got.clone()).collect();
+        assert_eq!(got, [Ok("142".to_owned()), Ok("281".to_owned())]);
+        assert!(checks.iter().all(Check::passed));
+    }
+
+    #[test]
+    fn days_without_answers_have_nothing_to_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let solver = InDir {
+            dir: dir.path().to_owned(),
+        };
+        assert!(check_day(&solver, &InputKind::ALL).unwrap().is_empty());
+    }
+}
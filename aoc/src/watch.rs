@@ -0,0 +1,257 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use aoc_common::{Error, Part, Solver};
+use serde::Deserialize;
+
+/// Modification time of every file under the watched paths.
+pub type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Each part's answer, or the error the day reported for it.
+pub type Answers = BTreeMap<u8, Result<String, String>>;
+
+/// The day's sources and manifest, the shared library the days build on and
+/// the input being solved.
+pub fn watched_paths(root: &Path, solver: &dyn Solver, input: &Path) -> Vec<PathBuf> {
+    let day_dir = root.join(format!("d{:02}", solver.day()));
+    vec![
+        day_dir.join("src"),
+        day_dir.join("Cargo.toml"),
+        root.join("aoc-common").join("src"),
+        input.to_owned(),
+    ]
+}
+
+pub fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    let mut files = Snapshot::new();
+    for path in paths {
+        collect(path, &mut files);
+    }
+    files
+}
+
+fn collect(path: &Path, files: &mut Snapshot) {
+    // Paths that vanish mid-walk are simply left out; their absence is
+    // itself a change
+    let Ok(metadata) = fs::metadata(path) else {
+        return;
+    };
+    if metadata.is_dir() {
+        if let Ok(entries) = fs::read_dir(path) {
+            for entry in entries.flatten() {
+                collect(&entry.path(), files);
+            }
+        }
+    } else if let Ok(modified) = metadata.modified() {
+        files.insert(path.to_owned(), modified);
+    }
+}
+
+/// Files that were added, removed or modified between two snapshots.
+pub fn changed(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = after
+        .iter()
+        .filter(|&(path, modified)| before.get(path) != Some(modified))
+        .map(|(path, _)| path.clone())
+        .collect();
+    paths.extend(
+        before
+            .keys()
+            .filter(|path| !after.contains_key(*path))
+            .cloned(),
+    );
+    paths.sort();
+    paths
+}
+
+#[derive(Deserialize)]
+struct Reported {
+    part: u8,
+    answer: Option<String>,
+    error: Option<String>,
+}
+
+/// Reads the `--format json` lines printed by a day binary.
+pub fn parse_report(stdout: &str) -> Answers {
+    stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Reported>(line).ok())
+        .map(|reported| {
+            let answer = match (reported.answer, reported.error) {
+                (Some(answer), _) => Ok(answer),
+                (None, error) => Err(error.unwrap_or_else(|| "no answer".to_owned())),
+            };
+            (reported.part, answer)
+        })
+        .collect()
+}
+
+/// Rebuilds the day binary through cargo and runs it. Compiler and solver
+/// errors go straight to the terminal; `Err` only says that nothing was
+/// answered.
+fn run_day(root: &Path, day: u8, parts: &[Part], input: &Path) -> Result<Answers, String> {
+    let mut command = Command::new(std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    command
+        .arg("run")
+        .arg("--release")
+        .arg("--quiet")
+        .arg("--manifest-path")
+        .arg(root.join("Cargo.toml"))
+        .args([
+            "-p",
+            &format!("d{:02}", day),
+            "--",
+            "--format",
+            "json",
+            "--input",
+        ])
+        .arg(input)
+        .stderr(Stdio::inherit());
+    if let [part] = parts {
+        command.args(["--part", &part.to_string()]);
+    }
+
+    let output = command
+        .output()
+        .map_err(|e| format!("cannot start cargo: {}", e))?;
+    let answers = parse_report(&String::from_utf8_lossy(&output.stdout));
+    if answers.is_empty() {
+        return Err(format!("build or run failed ({})", output.status));
+    }
+    Ok(answers)
+}
+
+fn print_comparison(previous: Option<&Answers>, answers: &Answers) {
+    let cell = |answer: Option<&Result<String, String>>| match answer {
+        Some(Ok(answer)) => answer.clone(),
+        Some(Err(e)) => format!("error: {}", e),
+        None => "-".to_owned(),
+    };
+    let rows: Vec<(u8, String, String)> = answers
+        .iter()
+        .map(|(&part, answer)| {
+            let before = cell(previous.and_then(|previous| previous.get(&part)));
+            (part, before, cell(Some(answer)))
+        })
+        .collect();
+    let width = rows
+        .iter()
+        .map(|(_, before, _)| before.len())
+        .max()
+        .unwrap_or(0)
+        .max("previous".len());
+
+    println!("{:<4} {:<width$}  new", "part", "previous");
+    for (part, before, after) in rows {
+        let marker = if previous.is_some() && before != after {
+            "  (changed)"
+        } else {
+            ""
+        };
+        println!("{:<4} {:<width$}  {}{}", part, before, after, marker);
+    }
+}
+
+/// Runs the day, then again every time one of its watched files changes,
+/// showing the answers next to those of the previous successful run. Only
+/// returns if the first snapshot finds nothing to watch.
+pub fn watch(
+    root: &Path,
+    solver: &dyn Solver,
+    parts: &[Part],
+    input: &Path,
+    interval: Duration,
+) -> Result<bool, Error> {
+    let paths = watched_paths(root, solver, input);
+    let mut seen = snapshot(&paths);
+    if seen.is_empty() {
+        return Err(Error::Invalid(format!(
+            "nothing to watch for day {}",
+            solver.day()
+        )));
+    }
+
+    let mut previous: Option<Answers> = None;
+    let mut reason = format!("watching {} files", seen.len());
+    loop {
+        println!("== {} ==", reason);
+        match run_day(root, solver.day(), parts, input) {
+            Ok(answers) => {
+                print_comparison(previous.as_ref(), &answers);
+                previous = Some(answers);
+            }
+            Err(e) => println!("{}, keeping the previous answers", e),
+        }
+
+        let changes = loop {
+            thread::sleep(interval);
+            let now = snapshot(&paths);
+            let changes = changed(&seen, &now);
+            if !changes.is_empty() {
+                seen = now;
+                break changes;
+            }
+        };
+        let names: Vec<String> = changes
+            .iter()
+            .map(|path| {
+                path.strip_prefix(root)
+                    .unwrap_or(path)
+                    .display()
+                    .to_string()
+            })
+            .collect();
+        reason = format!("changed: {}", names.join(", "));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_added_modified_and_removed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let kept = dir.path().join("kept.rs");
+        let edited = dir.path().join("edited.rs");
+        let removed = dir.path().join("removed.rs");
+        for path in [&kept, &edited, &removed] {
+            fs::write(path, "").unwrap();
+        }
+        let paths = vec![dir.path().to_owned()];
+        let before = snapshot(&paths);
+        assert_eq!(before.len(), 3);
+
+        let later = SystemTime::now() + Duration::from_secs(5);
+        fs::File::options()
+            .write(true)
+            .open(&edited)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        fs::remove_file(&removed).unwrap();
+        let added = dir.path().join("added.rs");
+        fs::write(&added, "").unwrap();
+
+        let after = snapshot(&paths);
+        assert_eq!(changed(&before, &after), vec![added, edited, removed]);
+        assert!(changed(&after, &after).is_empty());
+    }
+
+    #[test]
+    fn parses_json_report() {
+        let stdout = concat!(
+            r#"{"day":7,"part":1,"answer":"6440","elapsed_ms":0.1,"input_path":"t"}"#,
+            "\n",
+            r#"{"day":7,"part":2,"answer":null,"elapsed_ms":0.0,"input_path":"t","error":"nope"}"#,
+            "\nstray output\n"
+        );
+        let answers = parse_report(stdout);
+        assert_eq!(answers[&1], Ok("6440".to_owned()));
+        assert_eq!(answers[&2], Err("nope".to_owned()));
+    }
+}
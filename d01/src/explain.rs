@@ -0,0 +1,143 @@
+//! A line-by-line account of how each calibration value was worked out.
+
+use std::fmt;
+
+use crate::{calibration_value, Match, Words};
+
+/// A digit or number word found in a line, `start` being its byte offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub text: &'a str,
+    pub start: usize,
+    pub value: u32,
+}
+
+impl Token<'_> {
+    /// Whether the token was spelled out rather than written as a digit.
+    pub fn is_word(&self) -> bool {
+        !self.text.bytes().all(|byte| byte.is_ascii_digit())
+    }
+}
+
+impl fmt::Display for Token<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = if self.is_word() { "word" } else { "digit" };
+        write!(
+            f,
+            "{} {:?} at {}..{}",
+            kind,
+            self.text,
+            self.start,
+            self.start + self.text.len()
+        )
+    }
+}
+
+/// The first and last token each part read from one line.
+#[derive(Debug)]
+pub struct Explanation<'a> {
+    pub number: usize,
+    pub line: &'a str,
+    pub digits: Option<(Token<'a>, Token<'a>)>,
+    pub words: Option<(Token<'a>, Token<'a>)>,
+}
+
+fn value(tokens: Option<(Token, Token)>) -> Option<u32> {
+    tokens.map(|(first, last)| calibration_value(first.value, last.value))
+}
+
+impl<'a> Explanation<'a> {
+    /// Explains line `number` of the input, counting from 1.
+    pub fn new(number: usize, line: &'a str, words: &Words) -> Self {
+        let mut digits = line.char_indices().filter_map(|(start, c)| {
+            Some(Token {
+                text: &line[start..start + 1],
+                start,
+                value: c.to_digit(10)?,
+            })
+        });
+        let digits = digits
+            .next()
+            .map(|first| (first, digits.next_back().unwrap_or(first)));
+        let words = words.first_and_last(line).map(|(first, last)| {
+            let token = |found: Match| Token {
+                text: &line[found.start..found.end],
+                start: found.start,
+                value: found.value,
+            };
+            (token(first), token(last))
+        });
+        Explanation {
+            number,
+            line,
+            digits,
+            words,
+        }
+    }
+
+    pub fn part1(&self) -> Option<u32> {
+        value(self.digits)
+    }
+
+    pub fn part2(&self) -> Option<u32> {
+        value(self.words)
+    }
+
+    /// Whether the two parts read the line differently.
+    pub fn differs(&self) -> bool {
+        self.part1() != self.part2()
+    }
+}
+
+impl fmt::Display for Explanation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {} {:?}", self.number, self.line)?;
+        for (part, tokens) in [(1, self.digits), (2, self.words)] {
+            match tokens {
+                Some((first, last)) => write!(
+                    f,
+                    "\n  part {}: {:02} from {}, {}",
+                    part,
+                    calibration_value(first.value, last.value),
+                    first,
+                    last
+                )?,
+                None => write!(f, "\n  part {}: no digit", part)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explains_both_parts() {
+        let words = Words::new();
+        let explanation = Explanation::new(3, "xtwone3four", &words);
+        assert_eq!(
+            (explanation.part1(), explanation.part2()),
+            (Some(33), Some(24))
+        );
+        assert!(explanation.differs());
+        assert_eq!(
+            explanation.to_string().lines().collect::<Vec<_>>(),
+            [
+                "line 3 \"xtwone3four\"",
+                "  part 1: 33 from digit \"3\" at 6..7, digit \"3\" at 6..7",
+                "  part 2: 24 from word \"two\" at 1..4, word \"four\" at 7..11",
+            ]
+        );
+    }
+
+    #[test]
+    fn lines_without_words_agree() {
+        let explanation = Explanation::new(1, "a1b2c", &Words::new());
+        assert!(!explanation.differs());
+        let explanation = Explanation::new(2, "abc", &Words::new());
+        assert!(!explanation.differs());
+        assert!(explanation.to_string().ends_with("part 2: no digit"));
+    }
+}
@@ -0,0 +1,439 @@
+//! Day 1: Trebuchet?! Each calibration line's value is its first and last
+//! digit, with part 2 also counting digits spelled out as words. The words
+//! are English as in the puzzle, or any other [`Vocabulary`].
+
+use aoc_common::input::{self, LineStream};
+use aoc_common::{Answer, Error, Part, Rng, Solution};
+
+mod explain;
+mod missing;
+mod scanner;
+mod vocabulary;
+
+pub use explain::{Explanation, Token};
+pub use missing::NoDigit;
+pub use scanner::{Match, Scanner};
+pub use vocabulary::{extended_english, parse_words, Vocabulary, ENGLISH, FRENCH, GERMAN, SPANISH};
+
+/// The digit characters of each line, in order.
+pub fn get_digits(instructions: &[String]) -> Vec<String> {
+    instructions
+        .iter()
+        .map(|row| row.chars().filter(|c| c.is_ascii_digit()).collect())
+        .collect()
+}
+
+/// Sums the two-digit values formed by the first and last digit of each
+/// entry, as returned by [`get_digits`]. An empty entry is a line without a
+/// digit, dealt with by `policy`.
+pub fn sum_instructions(instructions: Vec<String>, policy: NoDigit) -> Result<u32, Error> {
+    let values = instructions
+        .iter()
+        .enumerate()
+        .map(|(i, digits)| (i + 1, digits.as_str(), digit_value(digits)));
+    policy.total(Part::One, values)
+}
+
+/// The part 1 value of one line: its first and last digit, if it has any.
+pub fn digit_value(line: &str) -> Option<u32> {
+    let mut digits = line.chars().filter_map(|c| c.to_digit(10));
+    let first = digits.next()?;
+    Some(first * 10 + digits.next_back().unwrap_or(first))
+}
+
+/// The digits of part 2, numeric or spelled out, built into one [`Scanner`]
+/// so a line is searched for all of them in a single pass.
+pub struct Words {
+    scanner: Scanner,
+}
+
+impl Words {
+    /// The numeric digits and the English words of the puzzle.
+    pub fn new() -> Self {
+        Words::with(ENGLISH.iter().copied().zip(1..), false)
+    }
+
+    /// The numeric digits and the given `(word, digit)` pairs.
+    pub fn with<'a, I: IntoIterator<Item = (&'a str, u32)>>(words: I, ignore_case: bool) -> Self {
+        let digits = DIGITS.iter().copied().zip(0..);
+        let patterns = digits.chain(words);
+        Words {
+            scanner: if ignore_case {
+                Scanner::ignoring_case(patterns)
+            } else {
+                Scanner::new(patterns)
+            },
+        }
+    }
+
+    /// The first and last number of a line, if it holds any.
+    pub fn first_and_last(&self, line: &str) -> Option<(Match, Match)> {
+        self.scanner.first_and_last(line)
+    }
+
+    /// The part 2 value of one line, if it holds a digit, written out or not.
+    pub fn line_value(&self, line: &str) -> Option<u32> {
+        let (first, last) = self.first_and_last(line)?;
+        Some(calibration_value(first.value, last.value))
+    }
+}
+
+impl Default for Words {
+    fn default() -> Self {
+        Words::new()
+    }
+}
+
+/// The two-digit value of a line whose first number is `first` and last is
+/// `last`. A number of several digits, such as `twelve` with
+/// [`extended_english`], is read as it would be written in digits: as the
+/// first number it gives its leading digit, as the last its final one, so
+/// `twelve` alone gives 12 and `twentythree` alone 23.
+pub fn calibration_value(first: u32, last: u32) -> u32 {
+    let mut leading = first;
+    while leading >= 10 {
+        leading /= 10;
+    }
+    leading * 10 + last % 10
+}
+
+/// The calibration sum of part 2, where `one` to `nine` count as digits.
+/// Lines without a digit are dealt with by `policy`.
+pub fn find_numbers(instructions: &[String], policy: NoDigit) -> Result<u32, Error> {
+    let words = Words::new();
+    let values = instructions
+        .iter()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.as_str(), words.line_value(line)));
+    policy.total(Part::Two, values)
+}
+
+const DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+fn random_digit(rng: &mut Rng) -> char {
+    char::from(b'1' + rng.below(9) as u8)
+}
+
+/// `size` lines mixing letters, digits and spelled-out digits, each holding at
+/// least one real digit so both parts have a value for every line.
+fn generate_document(rng: &mut Rng, size: usize) -> String {
+    let mut lines = Vec::with_capacity(size);
+    for _ in 0..size {
+        let mut line = String::new();
+        for _ in 0..rng.range(1..10) {
+            match rng.below(4) {
+                0 => line.push_str(ENGLISH[rng.below(ENGLISH.len())]),
+                1 => line.push(random_digit(rng)),
+                _ => line.push(char::from(b'a' + rng.below(26) as u8)),
+            }
+        }
+        if !line.chars().any(|c| c.is_ascii_digit()) {
+            let at = rng.below(line.len() + 1);
+            line.insert(at, random_digit(rng));
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+/// Both readings of one calibration line, each `None` when the line holds
+/// no digit of that kind.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Calibration {
+    /// The line's number in the input, counting from 1.
+    pub line: usize,
+    /// The line itself, only kept when a reading is missing, for the error
+    /// that names it.
+    pub text: Option<String>,
+    /// Counting only numeric digits, as in part 1.
+    pub digits: Option<u32>,
+    /// Counting spelled-out digits too, as in part 2.
+    pub words: Option<u32>,
+}
+
+impl Calibration {
+    pub fn new(number: usize, line: &str, words: &Words) -> Self {
+        let digits = digit_value(line);
+        let words = words.line_value(line);
+        Calibration {
+            line: number,
+            text: (digits.is_none() || words.is_none()).then(|| line.to_owned()),
+            digits,
+            words,
+        }
+    }
+
+    /// The kept text of the line, empty when both readings have a value.
+    pub fn text(&self) -> &str {
+        self.text.as_deref().unwrap_or_default()
+    }
+}
+
+/// The `log` target of the line explanations.
+pub const TRACE_TARGET: &str = "d01::trace";
+
+/// Which lines to explain while parsing. Explanations are logged at info
+/// level to [`TRACE_TARGET`], so they only appear with `-v` or more.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Trace {
+    #[default]
+    Off,
+    All,
+    /// Only the lines whose part 1 and part 2 values differ.
+    Differing,
+}
+
+/// Day 1, with the puzzle's English words matched exactly unless told
+/// otherwise.
+#[derive(Debug, Clone, Default)]
+pub struct Day01 {
+    pub vocabulary: Vocabulary,
+    /// Match the words whatever the case of their letters.
+    pub ignore_case: bool,
+    /// Also count numbers beyond nine: the English ones of
+    /// [`extended_english`], or those of a vocabulary file.
+    pub extended: bool,
+    pub trace: Trace,
+    pub no_digit: NoDigit,
+}
+
+impl Day01 {
+    /// The puzzle as set, for where a constant is needed.
+    pub const PUZZLE: Day01 = Day01 {
+        vocabulary: Vocabulary::English,
+        ignore_case: false,
+        extended: false,
+        trace: Trace::Off,
+        no_digit: NoDigit::Error,
+    };
+
+    pub fn words(&self) -> Result<Words, Error> {
+        let mut words = self.vocabulary.words(self.extended)?;
+        if self.extended {
+            match self.vocabulary {
+                Vocabulary::English => words.extend(extended_english()),
+                Vocabulary::File(_) => {}
+                _ => {
+                    return Err(Error::Invalid(format!(
+                        "extended numerals are not defined for {}",
+                        self.vocabulary
+                    )))
+                }
+            }
+        }
+        Ok(Words::with(
+            words.iter().map(|(word, digit)| (word.as_str(), *digit)),
+            self.ignore_case,
+        ))
+    }
+}
+
+impl Solution for Day01 {
+    type Input = Vec<Calibration>;
+
+    const DAY: u8 = 1;
+
+    const DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    const EXAMPLE: Option<&'static str> = Some("test.txt");
+
+    const EXAMPLE_2: Option<&'static str> = Some("test_2.txt");
+
+    fn parse(&self, file: &str) -> Result<Self::Input, Error> {
+        self.parse_lines(&mut input::str_lines(file))
+    }
+
+    /// Works out both parts' value of each line as it is read, so only two
+    /// numbers per line are kept, along with the text of a line lacking one.
+    fn parse_lines(&self, lines: &mut LineStream) -> Result<Self::Input, Error> {
+        let words = self.words()?;
+        input::records(lines)
+            .map(|line| {
+                let (number, line) = line?;
+                if self.trace != Trace::Off
+                    && log::log_enabled!(target: TRACE_TARGET, log::Level::Info)
+                {
+                    let explanation = Explanation::new(number, &line, &words);
+                    if self.trace == Trace::All || explanation.differs() {
+                        log::info!(target: TRACE_TARGET, "{}", explanation);
+                    }
+                }
+                Ok(Calibration::new(number, &line, &words))
+            })
+            .collect()
+    }
+
+    fn part1(&self, data: &Self::Input) -> Result<Answer, Error> {
+        let values = data
+            .iter()
+            .map(|line| (line.line, line.text(), line.digits));
+        Ok(self.no_digit.total(Part::One, values)?.into())
+    }
+
+    fn part2(&self, data: &Self::Input) -> Result<Answer, Error> {
+        let values = data.iter().map(|line| (line.line, line.text(), line.words));
+        Ok(self.no_digit.total(Part::Two, values)?.into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Result<String, Error> {
+        Ok(generate_document(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{testing, Part};
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+    const EXAMPLE_2: &str = include_str!("../test_2.txt");
+
+    #[test]
+    fn digits_pair_first_and_last() {
+        let lines = input::lines("treb7uchet\npqr3stu8vwx");
+        assert_eq!(
+            sum_instructions(get_digits(&lines), NoDigit::Error).unwrap(),
+            77 + 38
+        );
+    }
+
+    #[test]
+    fn words_can_overlap() {
+        let lines = input::lines("xtwone3four\nzoneight234\neightwothree\ntwone\neightwo");
+        assert_eq!(
+            find_numbers(&lines, NoDigit::Error).unwrap(),
+            24 + 14 + 83 + 21 + 82
+        );
+    }
+
+    #[test]
+    fn lines_without_digits_follow_the_policy() {
+        let file = "1a\n\nabc\ntwo";
+        let data = Day01::default().parse(file).unwrap();
+        assert_eq!(
+            Day01::default().part1(&data).unwrap_err().to_string(),
+            "3:1: no digit for part 1: \"abc\""
+        );
+        assert_eq!(
+            Day01::default().part2(&data).unwrap_err().to_string(),
+            "3:1: no digit for part 2: \"abc\""
+        );
+        assert_eq!(data[0].text, None);
+        for no_digit in [NoDigit::Skip, NoDigit::Zero] {
+            let day = Day01 {
+                no_digit,
+                ..Day01::default()
+            };
+            assert_eq!(day.part1(&data).unwrap().as_str(), "11");
+            assert_eq!(day.part2(&data).unwrap().as_str(), "33");
+        }
+        let lines = vec!["".to_owned(), "7".to_owned()];
+        assert!(sum_instructions(lines.clone(), NoDigit::Error).is_err());
+        assert_eq!(sum_instructions(lines, NoDigit::Zero).unwrap(), 77);
+    }
+
+    #[test]
+    fn part1_example() {
+        testing::assert_answers(&Day01::default(), EXAMPLE, &[(Part::One, "142")]);
+    }
+
+    #[test]
+    fn part2_example() {
+        testing::assert_answers(&Day01::default(), EXAMPLE_2, &[(Part::Two, "281")]);
+    }
+
+    #[test]
+    fn part1_counts_the_second_example_with_zeros() {
+        // Part 1 of the second example meets a line without a digit
+        let day = Day01 {
+            no_digit: NoDigit::Zero,
+            ..Day01::default()
+        };
+        testing::assert_answers(&day, EXAMPLE_2, &[(Part::One, "209")]);
+    }
+
+    /// Straightforward scan of every position, to check `find_numbers`
+    /// against.
+    fn reference_value(line: &str) -> u32 {
+        let digits: Vec<u32> = (0..line.len())
+            .filter_map(|i| {
+                let rest = &line[i..];
+                rest.chars()
+                    .next()
+                    .and_then(|c| c.to_digit(10))
+                    .or_else(|| {
+                        let word = ENGLISH.iter().position(|word| rest.starts_with(word))?;
+                        Some(word as u32 + 1)
+                    })
+            })
+            .collect();
+        digits[0] * 10 + digits[digits.len() - 1]
+    }
+
+    #[test]
+    fn other_vocabularies() {
+        let spanish = Day01 {
+            vocabulary: Vocabulary::Spanish,
+            ignore_case: true,
+            ..Day01::default()
+        };
+        let data = spanish.parse("xDOS3uno\nochoveinte\n").unwrap();
+        assert_eq!(
+            spanish.part2(&data).unwrap().as_str(),
+            (21 + 88).to_string()
+        );
+
+        let english = Day01::default().parse("xDOS3uno\nOne2\n").unwrap();
+        assert_eq!(Day01::default().part2(&english).unwrap().as_str(), "55");
+    }
+
+    #[test]
+    fn extended_numerals() {
+        let extended = Day01 {
+            extended: true,
+            ..Day01::default()
+        };
+        let lines = [
+            ("twelve", 12),
+            ("xzero", 0),
+            ("twentythree", 23),
+            ("ninety-nine", 99),
+            ("seventeenfour", 14),
+            ("5sixty", 50),
+            ("eighteenzero", 10),
+            ("twentyone", 21),
+        ];
+        for (line, expected) in lines {
+            let data = extended.parse(line).unwrap();
+            assert_eq!(
+                extended.part2(&data).unwrap().as_str(),
+                expected.to_string(),
+                "{}",
+                line
+            );
+            let plain = Day01::default().parse(line).unwrap();
+            assert_eq!(plain.len(), 1);
+        }
+        let french = Day01 {
+            vocabulary: Vocabulary::French,
+            ..extended
+        };
+        assert!(french.parse("un").is_err());
+    }
+
+    #[test]
+    fn part2_matches_reference_on_generated_input() {
+        for seed in 0..20 {
+            let file = generate_document(&mut Rng::new(seed), 50);
+            let expected: u32 = file.lines().map(reference_value).sum();
+            let data = Day01::default().parse(&file).unwrap();
+            assert_eq!(
+                Day01::default().part2(&data).unwrap().as_str(),
+                expected.to_string(),
+                "seed {}",
+                seed
+            );
+        }
+    }
+}
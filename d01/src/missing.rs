@@ -0,0 +1,137 @@
+//! What to do with calibration lines that hold no digit at all.
+
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_common::{Error, ParseError, Part};
+
+/// How many affected line numbers a summary lists before cutting off.
+const LISTED: usize = 10;
+
+/// The policy for a line without a digit, the same for both parts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NoDigit {
+    /// Fail the part, naming the first such line.
+    #[default]
+    Error,
+    /// Leave the line out of the sum.
+    Skip,
+    /// Count the line as a value of 0. The sum comes out as with
+    /// [`NoDigit::Skip`]; only the summary differs.
+    Zero,
+}
+
+impl FromStr for NoDigit {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "error" => Ok(NoDigit::Error),
+            "skip" => Ok(NoDigit::Skip),
+            "zero" => Ok(NoDigit::Zero),
+            _ => Err(format!(
+                "unknown policy `{}`, expected error, skip or zero",
+                name
+            )),
+        }
+    }
+}
+
+impl fmt::Display for NoDigit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            NoDigit::Error => "error",
+            NoDigit::Skip => "skip",
+            NoDigit::Zero => "zero",
+        })
+    }
+}
+
+impl NoDigit {
+    /// Sums one part's line values, given with their line numbers and text,
+    /// handling the lines without one by the policy. Any lines skipped or counted
+    /// as zero are summed up in a warning.
+    pub fn total<'a, I>(self, part: Part, values: I) -> Result<u32, Error>
+    where
+        I: IntoIterator<Item = (usize, &'a str, Option<u32>)>,
+    {
+        let mut total = 0;
+        let mut missing = Vec::new();
+        for (line, text, value) in values {
+            match (value, self) {
+                (Some(value), _) => total += value,
+                (None, NoDigit::Error) => {
+                    let message = format!("no digit for part {}", part);
+                    return Err(ParseError::at(text, text, message).on_line(line).into());
+                }
+                (None, NoDigit::Skip | NoDigit::Zero) => missing.push(line),
+            }
+        }
+        if !missing.is_empty() {
+            log::warn!("part {}: {}", part, self.summary(&missing));
+        }
+        Ok(total)
+    }
+
+    /// Describes the lines without a digit, e.g. `2 lines without a digit
+    /// were skipped: 3, 8`.
+    pub fn summary(self, lines: &[usize]) -> String {
+        let mut listed: Vec<String> = lines.iter().take(LISTED).map(usize::to_string).collect();
+        if lines.len() > LISTED {
+            listed.push("...".to_owned());
+        }
+        let action = match self {
+            NoDigit::Error => "rejected",
+            NoDigit::Skip => "skipped",
+            NoDigit::Zero => "counted as zero",
+        };
+        format!(
+            "{} {} without a digit {} {}: {}",
+            lines.len(),
+            if lines.len() == 1 { "line" } else { "lines" },
+            if lines.len() == 1 { "was" } else { "were" },
+            action,
+            listed.join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALUES: [(usize, &str, Option<u32>); 3] = [
+        (1, "a1b2", Some(12)),
+        (3, "abc", None),
+        (4, "3x0", Some(30)),
+    ];
+
+    #[test]
+    fn policies() {
+        let error = NoDigit::Error.total(Part::One, VALUES).unwrap_err();
+        assert_eq!(
+            error.in_file("calibration.txt").to_string(),
+            "calibration.txt:3:1: no digit for part 1: \"abc\""
+        );
+        assert_eq!(NoDigit::Skip.total(Part::Two, VALUES).unwrap(), 42);
+        assert_eq!(NoDigit::Zero.total(Part::Two, VALUES).unwrap(), 42);
+        assert_eq!("zero".parse::<NoDigit>(), Ok(NoDigit::Zero));
+    }
+
+    #[test]
+    fn summaries() {
+        assert_eq!(
+            NoDigit::Skip.summary(&[3]),
+            "1 line without a digit was skipped: 3"
+        );
+        let lines: Vec<usize> = (1..=12).collect();
+        assert_eq!(
+            NoDigit::Skip.summary(&lines),
+            "12 lines without a digit were skipped: 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, ..."
+        );
+        assert_eq!(
+            NoDigit::Zero.summary(&[3, 8]),
+            "2 lines without a digit were counted as zero: 3, 8"
+        );
+    }
+}
@@ -0,0 +1,219 @@
+//! A multi-pattern matcher in the style of Aho-Corasick: the patterns are
+//! built into a trie whose nodes also know where to resume after a
+//! mismatch, so one pass over a line finds every occurrence of every
+//! pattern, overlapping ones included. Matching is done on characters, but
+//! matches are reported by byte offset into the line.
+
+use std::collections::{BTreeMap, VecDeque};
+
+/// One occurrence of a pattern in a line, `start..end` being its byte range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub start: usize,
+    pub end: usize,
+    pub value: u32,
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    children: BTreeMap<char, usize>,
+    /// The node for the longest proper suffix of this one's text that is
+    /// also in the trie.
+    fail: usize,
+    /// The value and length in characters of the pattern ending here, if
+    /// any.
+    pattern: Option<(u32, usize)>,
+    /// The nearest node along the fail links that ends a pattern.
+    output: Option<usize>,
+}
+
+const ROOT: usize = 0;
+
+/// Lowercases `c` when case is ignored, unless that takes more than one
+/// character.
+fn fold(c: char, ignore_case: bool) -> char {
+    let mut lower = c.to_lowercase();
+    match (ignore_case, lower.next(), lower.next()) {
+        (true, Some(lower), None) => lower,
+        _ => c,
+    }
+}
+
+#[derive(Debug)]
+pub struct Scanner {
+    nodes: Vec<Node>,
+    ignore_case: bool,
+}
+
+impl Scanner {
+    /// A scanner for the given `(pattern, value)` pairs. Empty patterns are
+    /// ignored, and a repeated pattern keeps its last value.
+    pub fn new<'a, I: IntoIterator<Item = (&'a str, u32)>>(patterns: I) -> Self {
+        Scanner::build(patterns, false)
+    }
+
+    /// Like [`Scanner::new`], but letters match whatever their case.
+    pub fn ignoring_case<'a, I: IntoIterator<Item = (&'a str, u32)>>(patterns: I) -> Self {
+        Scanner::build(patterns, true)
+    }
+
+    fn build<'a, I: IntoIterator<Item = (&'a str, u32)>>(patterns: I, ignore_case: bool) -> Self {
+        let mut scanner = Scanner {
+            nodes: vec![Node::default()],
+            ignore_case,
+        };
+        for (pattern, value) in patterns {
+            if pattern.is_empty() {
+                continue;
+            }
+            let mut node = ROOT;
+            for c in pattern.chars().map(|c| fold(c, ignore_case)) {
+                let nodes = &mut scanner.nodes;
+                node = match nodes[node].children.get(&c) {
+                    Some(&child) => child,
+                    None => {
+                        nodes.push(Node::default());
+                        let child = nodes.len() - 1;
+                        nodes[node].children.insert(c, child);
+                        child
+                    }
+                };
+            }
+            scanner.nodes[node].pattern = Some((value, pattern.chars().count()));
+        }
+        scanner.link();
+        scanner
+    }
+
+    /// Fills in the fail and output links once every pattern is in the trie.
+    fn link(&mut self) {
+        let nodes = &mut self.nodes;
+
+        // Breadth first, so every shorter suffix already has its links
+        let mut queue: VecDeque<usize> = nodes[ROOT].children.values().copied().collect();
+        while let Some(node) = queue.pop_front() {
+            let children: Vec<(char, usize)> = nodes[node]
+                .children
+                .iter()
+                .map(|(&c, &child)| (c, child))
+                .collect();
+            for (c, child) in children {
+                let mut fail = nodes[node].fail;
+                let fail = loop {
+                    match nodes[fail].children.get(&c) {
+                        Some(&next) => break next,
+                        _ if fail == ROOT => break ROOT,
+                        _ => fail = nodes[fail].fail,
+                    }
+                };
+                nodes[child].fail = fail;
+                nodes[child].output = if nodes[fail].pattern.is_some() {
+                    Some(fail)
+                } else {
+                    nodes[fail].output
+                };
+                queue.push_back(child);
+            }
+        }
+    }
+
+    fn step(&self, mut node: usize, c: char) -> usize {
+        let c = fold(c, self.ignore_case);
+        loop {
+            if let Some(&next) = self.nodes[node].children.get(&c) {
+                return next;
+            }
+            if node == ROOT {
+                return ROOT;
+            }
+            node = self.nodes[node].fail;
+        }
+    }
+
+    /// Every match in `line`, ordered by where they end and, for matches
+    /// ending together, longest first.
+    pub fn matches<'a>(&'a self, line: &'a str) -> impl Iterator<Item = Match> + 'a {
+        let mut node = ROOT;
+        line.char_indices().flat_map(move |(i, c)| {
+            node = self.step(node, c);
+            let end = i + c.len_utf8();
+            let mut found = Some(node).filter(|&node| self.nodes[node].pattern.is_some());
+            found = found.or(self.nodes[node].output);
+            std::iter::from_fn(move || {
+                let at = found?;
+                found = self.nodes[at].output;
+                let (value, len) = self.nodes[at].pattern?;
+                let (start, _) = line[..end].char_indices().nth_back(len - 1)?;
+                Some(Match { start, end, value })
+            })
+        })
+    }
+
+    /// The match starting first and the one starting last, preferring the
+    /// longer of two starting at the same place. `None` if nothing matches.
+    pub fn first_and_last(&self, line: &str) -> Option<(Match, Match)> {
+        let mut matches = self.matches(line);
+        let first = matches.next()?;
+        Some(matches.fold((first, first), |(first, last), found| {
+            let earlier = (found.start, std::cmp::Reverse(found.end))
+                < (first.start, std::cmp::Reverse(first.end));
+            let later = (found.start, found.end) > (last.start, last.end);
+            (
+                if earlier { found } else { first },
+                if later { found } else { last },
+            )
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(scanner: &Scanner, line: &str) -> Vec<(usize, u32)> {
+        scanner.matches(line).map(|m| (m.start, m.value)).collect()
+    }
+
+    #[test]
+    fn finds_overlapping_matches() {
+        let scanner = Scanner::new([("one", 1), ("two", 2), ("eight", 8), ("2", 2)]);
+        assert_eq!(values(&scanner, "xtwone2"), [(1, 2), (3, 1), (6, 2)]);
+        assert_eq!(values(&scanner, "eightwo"), [(0, 8), (4, 2)]);
+    }
+
+    #[test]
+    fn finds_patterns_inside_others() {
+        let scanner = Scanner::new([("he", 1), ("she", 2), ("hers", 3), ("e", 4)]);
+        assert_eq!(values(&scanner, "ushers"), [(1, 2), (2, 1), (3, 4), (2, 3)]);
+        let (first, last) = scanner.first_and_last("ushers").unwrap();
+        assert_eq!((first.value, last.value), (2, 4));
+    }
+
+    #[test]
+    fn offsets_are_in_bytes() {
+        let scanner = Scanner::ignoring_case([("fünf", 5), ("drei", 3)]);
+        let found: Vec<Match> = scanner.matches("éFÜNFdrei").collect();
+        assert_eq!(
+            found,
+            [
+                Match {
+                    start: 2,
+                    end: 7,
+                    value: 5
+                },
+                Match {
+                    start: 7,
+                    end: 11,
+                    value: 3
+                }
+            ]
+        );
+        assert_eq!(Scanner::new([("fünf", 5)]).matches("FÜNF").count(), 0);
+    }
+
+    #[test]
+    fn no_match() {
+        let scanner = Scanner::new([("one", 1)]);
+        assert_eq!(scanner.first_and_last("onx on"), None);
+    }
+}
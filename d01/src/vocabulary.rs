@@ -0,0 +1,201 @@
+//! The number words part 2 looks for, in one of the bundled languages or
+//! read from a file.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use aoc_common::{input, Error, ParseError};
+
+pub const ENGLISH: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+pub const FRENCH: [&str; 9] = [
+    "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf",
+];
+
+pub const GERMAN: [&str; 9] = [
+    "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+];
+
+pub const SPANISH: [&str; 9] = [
+    "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve",
+];
+
+/// Where the words for one to nine come from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Vocabulary {
+    #[default]
+    English,
+    French,
+    German,
+    Spanish,
+    /// A file of `<word> <value>` lines, with `#` starting a comment line.
+    /// A value may have several words. Values are single digits unless
+    /// extended numerals are asked for, when one of several digits counts as
+    /// described at [`calibration_value`](crate::calibration_value).
+    File(PathBuf),
+}
+
+impl FromStr for Vocabulary {
+    type Err = String;
+
+    /// A language name, or else the path of a vocabulary file.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Ok(match name.to_ascii_lowercase().as_str() {
+            "english" | "en" => Vocabulary::English,
+            "french" | "fr" => Vocabulary::French,
+            "german" | "de" => Vocabulary::German,
+            "spanish" | "es" => Vocabulary::Spanish,
+            _ => Vocabulary::File(PathBuf::from(name)),
+        })
+    }
+}
+
+impl fmt::Display for Vocabulary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Vocabulary::English => f.write_str("english"),
+            Vocabulary::French => f.write_str("french"),
+            Vocabulary::German => f.write_str("german"),
+            Vocabulary::Spanish => f.write_str("spanish"),
+            Vocabulary::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+fn numbered(words: &[&str; 9]) -> Vec<(String, u32)> {
+    words.iter().map(|&word| word.to_owned()).zip(1..).collect()
+}
+
+impl Vocabulary {
+    /// Each word with the number it stands for. With `extended`, a
+    /// vocabulary file may hold numbers of several digits.
+    pub fn words(&self, extended: bool) -> Result<Vec<(String, u32)>, Error> {
+        match self {
+            Vocabulary::English => Ok(numbered(&ENGLISH)),
+            Vocabulary::French => Ok(numbered(&FRENCH)),
+            Vocabulary::German => Ok(numbered(&GERMAN)),
+            Vocabulary::Spanish => Ok(numbered(&SPANISH)),
+            Vocabulary::File(path) => {
+                let file = input::read_to_string(path)?;
+                parse_words(&file, extended).map_err(|e| e.in_file(path.display()).into())
+            }
+        }
+    }
+}
+
+const TEENS: [&str; 10] = [
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: [&str; 8] = [
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+/// The English numerals beyond one to nine: `zero`, `ten` to `nineteen`, the
+/// tens from `twenty` to `ninety`, and every compound of a ten and a unit,
+/// written either joined up (`twentythree`) or hyphenated (`twenty-three`).
+///
+/// Where numerals start at the same place the longest one is read, so
+/// `seventeen` is never `seven`; a unit ending a compound, like the `three`
+/// of `twentythree`, ends it with the same digit, so either reading gives the
+/// same calibration value.
+pub fn extended_english() -> Vec<(String, u32)> {
+    let mut words = vec![("zero".to_owned(), 0)];
+    words.extend(TEENS.iter().map(|&teen| teen.to_owned()).zip(10..));
+    for (&ten, tens) in TENS.iter().zip((20..).step_by(10)) {
+        words.push((ten.to_owned(), tens));
+        for (&unit, units) in ENGLISH.iter().zip(1..) {
+            words.push((format!("{}{}", ten, unit), tens + units));
+            words.push((format!("{}-{}", ten, unit), tens + units));
+        }
+    }
+    words
+}
+
+/// The entries of a vocabulary file, whose values must be single digits
+/// unless `extended`.
+pub fn parse_words(file: &str, extended: bool) -> Result<Vec<(String, u32)>, ParseError> {
+    let mut words = Vec::new();
+    for (i, line) in input::lines(file).iter().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (word, value) = line
+            .split_once(char::is_whitespace)
+            .ok_or_else(|| ParseError::at(line, line, "expected `<word> <value>`"))
+            .map_err(|e| e.on_line(i + 1))?;
+        let value = value.trim();
+        let message = if extended {
+            "expected a number"
+        } else {
+            "expected a digit"
+        };
+        let number = value
+            .parse::<u32>()
+            .ok()
+            .filter(|&number| extended || number <= 9)
+            .ok_or_else(|| ParseError::at(line, value, message).on_line(i + 1))?;
+        words.push((word.to_owned(), number));
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_names_and_paths() {
+        assert_eq!("German".parse(), Ok(Vocabulary::German));
+        assert_eq!("es".parse(), Ok(Vocabulary::Spanish));
+        assert_eq!(
+            "words/dutch.txt".parse(),
+            Ok(Vocabulary::File(PathBuf::from("words/dutch.txt")))
+        );
+    }
+
+    #[test]
+    fn extended_numerals_cover_zero_to_ninety_nine() {
+        let words = extended_english();
+        let mut values: Vec<u32> = words.iter().map(|&(_, value)| value).collect();
+        values.sort();
+        values.dedup();
+        assert_eq!(values.len(), 100 - 9);
+        assert!(words.contains(&("forty-two".to_owned(), 42)));
+        assert!(words.contains(&("ninetynine".to_owned(), 99)));
+    }
+
+    #[test]
+    fn parses_word_files() {
+        let words = parse_words("# Dutch\neen 1\n\ntwee\t2\nzero 0\n", false).unwrap();
+        assert_eq!(
+            words,
+            [
+                ("een".to_owned(), 1),
+                ("twee".to_owned(), 2),
+                ("zero".to_owned(), 0)
+            ]
+        );
+        let error = parse_words("een 1\ntwee 12\n", false).unwrap_err();
+        assert_eq!((error.line, error.text.as_str()), (2, "12"));
+        let error = parse_words("een 1\ntwee two\n", true).unwrap_err();
+        assert_eq!((error.line, error.text.as_str()), (2, "two"));
+        assert_eq!(
+            parse_words("twaalf 12\n", true).unwrap(),
+            [("twaalf".to_owned(), 12)]
+        );
+    }
+}
@@ -0,0 +1,235 @@
+//! Day 2: Cube Conundrum. Each game is a list of handfuls of red, green
+//! and blue cubes drawn from a bag.
+
+use aoc_common::input::{self, LineStream};
+use aoc_common::{Answer, Error, ParseError, Rng, Solution};
+use std::str::FromStr;
+
+/// The cubes of each colour shown in one handful.
+#[derive(Debug, Default)]
+pub struct Round {
+    pub red: i32,
+    pub green: i32,
+    pub blue: i32,
+}
+
+/// The bag's contents in part 1.
+pub const MAX_ROUND: Round = Round {
+    red: 12,
+    green: 13,
+    blue: 14,
+};
+
+impl FromStr for Round {
+    type Err = ParseError;
+
+    fn from_str(data: &str) -> Result<Self, Self::Err> {
+        let mut obj = Round::default();
+
+        let split_parts: Vec<&str> = data.split(",").map(|s| s.trim()).collect();
+
+        for part in split_parts {
+            let (number, colour) = part
+                .split_once(" ")
+                .ok_or_else(|| ParseError::at(data, part, "expected `<count> <colour>`"))?;
+            let qty = number
+                .parse::<i32>()
+                .map_err(|_| ParseError::at(data, number, "invalid cube count"))?;
+
+            match colour {
+                "red" => obj.red = qty,
+                "green" => obj.green = qty,
+                "blue" => obj.blue = qty,
+                _ => return Err(ParseError::at(data, colour, "no colour found")),
+            };
+        }
+        Ok(obj)
+    }
+}
+
+impl Round {
+    /// Whether the handful could come from a bag holding [`MAX_ROUND`].
+    pub fn within_max(&self) -> bool {
+        self.red <= MAX_ROUND.red && self.green <= MAX_ROUND.green && self.blue <= MAX_ROUND.blue
+    }
+}
+
+#[derive(Debug)]
+pub struct Game {
+    score: i32,
+    rounds: Vec<Round>,
+}
+
+impl Game {
+    /// Parses a `Game <id>: <round>; <round>...` line.
+    pub fn new(game_row: &str) -> Result<Self, ParseError> {
+        let (name, all_rounds) = game_row
+            .split_once(":")
+            .ok_or_else(|| ParseError::at(game_row, game_row, "expected `Game <id>: <rounds>`"))?;
+
+        let (_, score) = name
+            .split_once(" ")
+            .ok_or_else(|| ParseError::at(game_row, name, "expected `Game <id>`"))?;
+        let rounds: Vec<Round> = all_rounds
+            .split(";")
+            .map(|n| Round::from_str(n).map_err(|e| e.within(game_row, n)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            score: score
+                .parse::<i32>()
+                .map_err(|_| ParseError::at(game_row, score, "invalid game id"))?,
+            rounds,
+        })
+    }
+
+    pub fn id(&self) -> i32 {
+        self.score
+    }
+
+    pub fn rounds(&self) -> &[Round] {
+        &self.rounds
+    }
+
+    pub fn check_rounds(&self) -> bool {
+        self.rounds.iter().all(|round| round.within_max())
+    }
+
+    /// The product of the fewest cubes of each colour the game needs.
+    pub fn get_power(&self) -> i32 {
+        let mut max_red = 0;
+        let mut max_green = 0;
+        let mut max_blue = 0;
+
+        for round in &self.rounds {
+            if round.red > max_red {
+                max_red = round.red
+            }
+            if round.green > max_green {
+                max_green = round.green
+            }
+            if round.blue > max_blue {
+                max_blue = round.blue
+            }
+        }
+        max_red * max_green * max_blue
+    }
+}
+
+/// The sum of the ids of the games possible with [`MAX_ROUND`].
+pub fn sum_winning_games(games: &[Game]) -> i32 {
+    games
+        .iter()
+        .filter(|&game| game.check_rounds())
+        .map(|game| game.score)
+        .sum()
+}
+
+const COLOURS: [&str; 3] = ["red", "green", "blue"];
+
+/// `size` games of one to six rounds, each round drawing up to 20 cubes of
+/// one, two or all three colours.
+fn generate_games(rng: &mut Rng, size: usize) -> String {
+    let mut games = Vec::with_capacity(size);
+    for id in 1..=size {
+        let mut rounds = Vec::new();
+        for _ in 0..rng.range(1..7) {
+            let mut colours = COLOURS;
+            rng.shuffle(&mut colours);
+            let drawn = rng.range(1..4) as usize;
+            let cubes: Vec<String> = colours[..drawn]
+                .iter()
+                .map(|colour| format!("{} {}", rng.range(1..21), colour))
+                .collect();
+            rounds.push(cubes.join(", "));
+        }
+        games.push(format!("Game {}: {}", id, rounds.join("; ")));
+    }
+    games.join("\n")
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Game>;
+
+    const DAY: u8 = 2;
+
+    const DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    const EXAMPLE: Option<&'static str> = Some("test.txt");
+
+    fn parse(&self, file: &str) -> Result<Self::Input, Error> {
+        self.parse_lines(&mut input::str_lines(file))
+    }
+
+    fn parse_lines(&self, lines: &mut LineStream) -> Result<Self::Input, Error> {
+        input::records(lines)
+            .map(|line| {
+                let (number, row) = line?;
+                Ok(Game::new(&row).map_err(|e| e.on_line(number))?)
+            })
+            .collect()
+    }
+
+    fn part1(&self, games: &Self::Input) -> Result<Answer, Error> {
+        Ok(sum_winning_games(games).into())
+    }
+
+    fn part2(&self, games: &Self::Input) -> Result<Answer, Error> {
+        let power: i32 = games.iter().map(|game| game.get_power()).sum();
+        Ok(power.into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Result<String, Error> {
+        Ok(generate_games(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{testing, Part};
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn round_from_str() {
+        let round = Round::from_str(" 3 blue, 4 red").unwrap();
+        assert_eq!((round.red, round.green, round.blue), (4, 0, 3));
+        assert!(round.within_max());
+    }
+
+    #[test]
+    fn round_rejects_unknown_colour() {
+        let error = Round::from_str("3 blue, 2 purple").unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (11, "purple"));
+    }
+
+    #[test]
+    fn game_power() {
+        let game = Game::new("Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green").unwrap();
+        assert_eq!(game.score, 3);
+        assert!(!game.check_rounds());
+        assert_eq!(game.get_power(), 20 * 13 * 6);
+    }
+
+    #[test]
+    fn part1_example() {
+        testing::assert_answers(&Day02, EXAMPLE, &[(Part::One, "8")]);
+    }
+
+    #[test]
+    fn part2_example() {
+        testing::assert_answers(&Day02, EXAMPLE, &[(Part::Two, "2286")]);
+    }
+
+    #[test]
+    fn generated_games_parse() {
+        let file = generate_games(&mut Rng::new(3), 40);
+        let games = Day02.parse(&file).unwrap();
+        assert_eq!(games.len(), 40);
+        assert!(Day02.part2(&games).is_ok());
+    }
+}
@@ -0,0 +1,325 @@
+//! Day 3: Gear Ratios. Part numbers in an engine schematic are the numbers
+//! touching a symbol, and gears are `*`s touching exactly two of them.
+
+use aoc_common::{input, Answer, Error, ParseError, Rng, Solution};
+use std::collections::{HashMap, HashSet};
+
+const NEIGHBOURHOOD: [[i32; 2]; 8] = [
+    [-1, -1],
+    [-1, 0],
+    [-1, 1],
+    [0, -1],
+    [0, 1],
+    [1, -1],
+    [1, 0],
+    [1, 1],
+];
+
+/// Row and column positions in the schematic, counting from 0.
+pub type Coords = HashSet<(i32, i32)>;
+
+/// The position of one digit of a number.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct Digit {
+    pub i: i32,
+    pub j: i32,
+}
+
+impl Digit {
+    pub fn new(i: i32, j: i32) -> Self {
+        Self { i, j }
+    }
+
+    pub fn has_neighbour(&self, symbol_indices: &Coords) -> bool {
+        for coord in NEIGHBOURHOOD {
+            if symbol_indices.contains(&(self.i + coord[0], self.j + coord[1])) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[derive(Debug, Default, Hash, PartialEq, Eq, Clone)]
+pub struct Number {
+    id: i32,
+    value: i32,
+    digit_coords: Vec<Digit>,
+}
+
+impl Number {
+    pub fn new(id: i32, str_number: &str, digits: &[Digit]) -> Result<Self, ParseError> {
+        let value = str_number.parse::<i32>().map_err(|_| {
+            let first = digits[0];
+            ParseError::new(
+                first.i as usize + 1,
+                first.j as usize + 1,
+                str_number,
+                "number too large",
+            )
+        })?;
+        Ok(Number {
+            id,
+            value,
+            digit_coords: digits.to_vec(),
+        })
+    }
+
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+
+    pub fn digits(&self) -> &[Digit] {
+        &self.digit_coords
+    }
+
+    /// Whether any digit touches a symbol, making this a part number.
+    pub fn is_valid(&self, symbol_indices: &Coords) -> bool {
+        self.digit_coords
+            .iter()
+            .any(|&d| d.has_neighbour(symbol_indices))
+    }
+}
+
+/// Finds the numbers in a schematic grid along with the positions of every
+/// symbol and of the `*`s among them.
+pub fn build_maps(data: Vec<Vec<char>>) -> Result<(Vec<Number>, Coords, Coords), ParseError> {
+    let mut seen_chars: Coords = HashSet::new();
+    let mut numbers: Vec<Number> = Vec::new();
+    let mut star_map: Coords = HashSet::new();
+
+    let mut number_started: bool = false;
+    let mut str_number = String::new();
+    let mut digits: Vec<Digit> = Vec::new();
+    let mut seen_numbers = 0;
+
+    for (i, row) in data.iter().enumerate() {
+        if number_started {
+            numbers.push(Number::new(seen_numbers, &str_number, &digits)?);
+            number_started = false;
+            str_number.clear();
+            digits.clear();
+            seen_numbers += 1;
+        }
+        for (j, col_value) in row.iter().enumerate() {
+            if col_value.is_ascii_digit() {
+                number_started = true;
+                str_number.push(*col_value);
+                digits.push(Digit::new(i as i32, j as i32));
+            } else {
+                if number_started {
+                    numbers.push(Number::new(seen_numbers, &str_number, &digits)?);
+                    number_started = false;
+                    str_number.clear();
+                    digits.clear();
+                    seen_numbers += 1;
+                }
+                if *col_value != '.' {
+                    seen_chars.insert((i as i32, j as i32));
+                }
+                if *col_value == '*' {
+                    star_map.insert((i as i32, j as i32));
+                }
+            }
+        }
+    }
+    // A number can also end the last row
+    if number_started {
+        numbers.push(Number::new(seen_numbers, &str_number, &digits)?);
+    }
+    Ok((numbers, seen_chars, star_map))
+}
+
+/// Maps each digit position to the number it belongs to.
+pub fn build_inverted_number_map(numbers: &[Number]) -> HashMap<(i32, i32), Number> {
+    let mut inverted_map: HashMap<(i32, i32), Number> = HashMap::new();
+    for number in numbers {
+        for digit in &number.digit_coords {
+            inverted_map.insert((digit.i, digit.j), number.clone());
+        }
+    }
+    inverted_map
+}
+
+/// The sum of the gear ratios of every `*` touching exactly two numbers.
+pub fn find_gears(star_map: &Coords, number_map: &HashMap<(i32, i32), Number>) -> i32 {
+    let mut seen_numbers: HashSet<&Number> = HashSet::new();
+    let mut total = 0;
+
+    for star in star_map {
+        for neighbour in NEIGHBOURHOOD {
+            if number_map.contains_key(&(star.0 + neighbour[0], star.1 + neighbour[1])) {
+                let number = number_map
+                    .get(&(star.0 + neighbour[0], star.1 + neighbour[1]))
+                    .unwrap();
+                seen_numbers.insert(number);
+            }
+        }
+        if seen_numbers.len() == 2 {
+            let mut inner_total = 1;
+            for &number in &seen_numbers {
+                inner_total *= number.value;
+            }
+            total += inner_total
+        }
+        seen_numbers.clear();
+    }
+    total
+}
+
+/// A parsed schematic: its numbers and symbol positions.
+pub struct Schematic {
+    numbers: Vec<Number>,
+    symbol_indices: Coords,
+    star_map: Coords,
+}
+
+const SYMBOLS: [char; 10] = ['*', '#', '+', '$', '/', '@', '=', '%', '-', '&'];
+
+/// A `size` by `size` schematic of one to three digit part numbers and
+/// symbols scattered over a field of dots.
+fn generate_schematic(rng: &mut Rng, size: usize) -> String {
+    let side = size.max(1);
+    let mut rows = Vec::with_capacity(side);
+    for _ in 0..side {
+        let mut row = String::with_capacity(side);
+        while row.len() < side {
+            if rng.chance(0.15) {
+                let digits = rng.below(3.min(side - row.len())) + 1;
+                row.push(char::from(b'1' + rng.below(9) as u8));
+                for _ in 1..digits {
+                    row.push(char::from(b'0' + rng.below(10) as u8));
+                }
+                // Keep the next number from running into this one
+                if row.len() < side {
+                    row.push('.');
+                }
+            } else if rng.chance(0.1) {
+                row.push(*rng.pick(&SYMBOLS));
+            } else {
+                row.push('.');
+            }
+        }
+        rows.push(row);
+    }
+    rows.join("\n")
+}
+
+impl Schematic {
+    pub fn numbers(&self) -> &[Number] {
+        &self.numbers
+    }
+
+    pub fn symbol_indices(&self) -> &Coords {
+        &self.symbol_indices
+    }
+
+    pub fn star_map(&self) -> &Coords {
+        &self.star_map
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Schematic;
+
+    const DAY: u8 = 3;
+
+    const DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    const EXAMPLE: Option<&'static str> = Some("test_file.txt");
+
+    fn parse(&self, file: &str) -> Result<Self::Input, Error> {
+        let (numbers, symbol_indices, star_map) = build_maps(input::grid(file))?;
+        Ok(Schematic {
+            numbers,
+            symbol_indices,
+            star_map,
+        })
+    }
+
+    fn part1(&self, schematic: &Self::Input) -> Result<Answer, Error> {
+        let total: i32 = schematic
+            .numbers
+            .iter()
+            .filter(|&x| x.is_valid(&schematic.symbol_indices))
+            .map(|x| x.value)
+            .sum();
+        Ok(total.into())
+    }
+
+    fn part2(&self, schematic: &Self::Input) -> Result<Answer, Error> {
+        let inverted_map = build_inverted_number_map(&schematic.numbers);
+        Ok(find_gears(&schematic.star_map, &inverted_map).into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Result<String, Error> {
+        Ok(generate_schematic(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{testing, Part};
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test_file.txt");
+
+    #[test]
+    fn build_maps_finds_numbers_and_symbols() {
+        let (numbers, symbols, stars) = build_maps(input::grid("467..\n...*.\n..35#")).unwrap();
+        let values: Vec<i32> = numbers.iter().map(|n| n.value).collect();
+        assert_eq!(values, vec![467, 35]);
+        assert_eq!(symbols, HashSet::from([(1, 3), (2, 4)]));
+        assert_eq!(stars, HashSet::from([(1, 3)]));
+        assert!(numbers.iter().all(|n| n.is_valid(&symbols)));
+    }
+
+    #[test]
+    fn number_ending_the_last_row_counts() {
+        for file in ["*...\n.123\n", "*...\n.123", "*...\n123.\n"] {
+            let data = Day03.parse(file).unwrap();
+            assert_eq!(Day03.part1(&data).unwrap().as_str(), "123", "{:?}", file);
+        }
+    }
+
+    #[test]
+    fn part1_example() {
+        testing::assert_answers(&Day03, EXAMPLE, &[(Part::One, "4361")]);
+    }
+
+    #[test]
+    fn part2_example() {
+        testing::assert_answers(&Day03, EXAMPLE, &[(Part::Two, "467835")]);
+    }
+
+    #[test]
+    fn generated_schematics_keep_every_number() {
+        let mut last_row_ends_in_number = false;
+        for seed in 0..20 {
+            let file = generate_schematic(&mut Rng::new(seed), 12);
+            let expected: Vec<i32> = file
+                .split(|c: char| !c.is_ascii_digit())
+                .filter(|run| !run.is_empty())
+                .map(|run| run.parse().unwrap())
+                .collect();
+            let data = Day03.parse(&file).unwrap();
+            let values: Vec<i32> = data.numbers.iter().map(|n| n.value).collect();
+            assert_eq!(values, expected, "seed {}", seed);
+            last_row_ends_in_number |= file.ends_with(|c: char| c.is_ascii_digit());
+        }
+        assert!(last_row_ends_in_number);
+    }
+
+    #[test]
+    fn generated_schematic_is_square() {
+        let file = generate_schematic(&mut Rng::new(5), 30);
+        assert_eq!(file.lines().count(), 30);
+        assert!(file.lines().all(|row| row.len() == 30));
+        let data = Day03.parse(&file).unwrap();
+        assert!(Day03.part1(&data).is_ok());
+        assert!(Day03.part2(&data).is_ok());
+    }
+}
@@ -0,0 +1,232 @@
+//! Day 4: Scratchcards. A card scores by how many of its drawn numbers are
+//! among its winning ones.
+
+use aoc_common::input::{self, LineStream};
+use aoc_common::{Answer, Error, ParseError, Rng, Solution};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+/// One scratchcard, parsed from `Card <id>: <winning> | <drawn>`.
+#[derive(Debug)]
+pub struct Card {
+    id: u32,
+    winning_nos: HashSet<u32>,
+    numbers: HashSet<u32>,
+}
+
+impl FromStr for Card {
+    type Err = ParseError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let parse_number = |n: &str| {
+            n.parse::<u32>()
+                .map_err(|_| ParseError::at(string, n, "invalid number"))
+        };
+
+        let (name, game) = string
+            .split_once(":")
+            .ok_or_else(|| ParseError::at(string, string, "expected `Card <id>: <numbers>`"))?;
+        let id = name
+            .split_whitespace()
+            .last()
+            .ok_or_else(|| ParseError::at(string, name, "missing card id"))?;
+        let number = parse_number(id)?;
+        let (winners, draw) = game
+            .split_once("|")
+            .ok_or_else(|| ParseError::at(string, game, "expected `<winners> | <draw>`"))?;
+
+        Ok(Card {
+            id: number,
+            winning_nos: winners
+                .split_whitespace()
+                .map(parse_number)
+                .collect::<Result<HashSet<u32>, _>>()?,
+            numbers: draw
+                .split_whitespace()
+                .map(parse_number)
+                .collect::<Result<HashSet<u32>, _>>()?,
+        })
+    }
+}
+
+impl Card {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn winning_numbers(&self) -> &HashSet<u32> {
+        &self.winning_nos
+    }
+
+    pub fn numbers(&self) -> &HashSet<u32> {
+        &self.numbers
+    }
+
+    /// How many drawn numbers are winning ones.
+    pub fn get_wins(&self) -> u32 {
+        self.numbers
+            .intersection(&self.winning_nos)
+            .collect::<Vec<&u32>>()
+            .len() as u32
+    }
+
+    /// The part 1 score: one point for the first match, doubled for each
+    /// further one.
+    pub fn get_score(&self) -> u32 {
+        let matching: u32 = self.get_wins();
+        if matching == 0 {
+            return 0_u32;
+        }
+        u32::pow(2, matching - 1)
+    }
+}
+
+pub fn build_cards(data: Vec<String>) -> Result<Vec<Card>, ParseError> {
+    data.iter()
+        .enumerate()
+        .map(|(i, line)| Card::from_str(line).map_err(|e| e.on_line(i + 1)))
+        .collect()
+}
+
+/// The total number of cards once every won copy has been played, as in
+/// part 2. Cards must be numbered from 1 in order.
+pub fn process_deck(cards: &[Card]) -> u32 {
+    let mut all_cards: HashMap<u32, u32> = HashMap::new();
+    let total_cards = cards.len() as u32;
+
+    for card in cards {
+        let qty_of_this_card = *all_cards.entry(card.id).or_insert(1);
+        let card_wins = card.get_wins();
+        // iterate for as many of this card we have
+        for _ in 0..qty_of_this_card {
+            for j in card.id..card.id + card_wins {
+                if j < total_cards {
+                    let next_card = &cards[j as usize];
+                    all_cards
+                        .entry(next_card.id)
+                        .and_modify(|e| *e += 1)
+                        .or_insert(2);
+                }
+            }
+        }
+    }
+    all_cards.iter().map(|c| c.1).sum::<u32>()
+}
+
+fn format_numbers(numbers: &[u32]) -> String {
+    let numbers: Vec<String> = numbers.iter().map(|n| format!("{:>2}", n)).collect();
+    numbers.join(" ")
+}
+
+/// `size` cards of ten winning numbers and 25 drawn ones, all below 100.
+///
+/// Most cards win nothing and the rest match at most three numbers, so the
+/// copies won in part 2 stay bounded however many cards there are.
+fn generate_deck(rng: &mut Rng, size: usize) -> String {
+    let width = size.to_string().len();
+    let mut cards = Vec::with_capacity(size);
+    for id in 1..=size {
+        let mut pool: Vec<u32> = (1..100).collect();
+        rng.shuffle(&mut pool);
+        let (winning, rest) = pool.split_at(10);
+
+        let matches = if rng.chance(0.6) {
+            0
+        } else {
+            rng.range(1..4) as usize
+        };
+        let matches = matches.min(size - id);
+        let mut drawn = [&winning[..matches], &rest[..25 - matches]].concat();
+        rng.shuffle(&mut drawn);
+
+        cards.push(format!(
+            "Card {:>width$}: {} | {}",
+            id,
+            format_numbers(winning),
+            format_numbers(&drawn),
+            width = width
+        ));
+    }
+    cards.join("\n")
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<Card>;
+
+    const DAY: u8 = 4;
+
+    const DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    const EXAMPLE: Option<&'static str> = Some("test_data.txt");
+
+    fn parse(&self, file: &str) -> Result<Self::Input, Error> {
+        self.parse_lines(&mut input::str_lines(file))
+    }
+
+    fn parse_lines(&self, lines: &mut LineStream) -> Result<Self::Input, Error> {
+        input::records(lines)
+            .map(|line| {
+                let (number, row) = line?;
+                Ok(Card::from_str(&row).map_err(|e| e.on_line(number))?)
+            })
+            .collect()
+    }
+
+    fn part1(&self, cards: &Self::Input) -> Result<Answer, Error> {
+        let score: u32 = cards.iter().map(|c| c.get_score()).sum();
+        Ok(score.into())
+    }
+
+    fn part2(&self, cards: &Self::Input) -> Result<Answer, Error> {
+        Ok(process_deck(cards).into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Result<String, Error> {
+        Ok(generate_deck(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{testing, Part};
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test_data.txt");
+
+    #[test]
+    fn card_from_str() {
+        let card = Card::from_str("Card  1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53").unwrap();
+        assert_eq!(card.id, 1);
+        assert_eq!(card.get_wins(), 4);
+        assert_eq!(card.get_score(), 8);
+    }
+
+    #[test]
+    fn card_without_divider_is_rejected() {
+        let error = Card::from_str("Card 1: 41 48 83").unwrap_err();
+        assert_eq!(error.column, 8);
+    }
+
+    #[test]
+    fn part1_example() {
+        testing::assert_answers(&Day04, EXAMPLE, &[(Part::One, "13")]);
+    }
+
+    #[test]
+    fn part2_example() {
+        testing::assert_answers(&Day04, EXAMPLE, &[(Part::Two, "30")]);
+    }
+
+    #[test]
+    fn generated_deck_parses() {
+        let file = generate_deck(&mut Rng::new(11), 120);
+        let cards = Day04.parse(&file).unwrap();
+        assert_eq!(cards.len(), 120);
+        assert!(cards.iter().all(|card| card.get_wins() <= 3));
+        assert_eq!(cards.last().unwrap().get_wins(), 0);
+        assert!(Day04.part2(&cards).is_ok());
+    }
+}
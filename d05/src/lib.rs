@@ -0,0 +1,375 @@
+//! Day 5: If You Give A Seed A Fertilizer. Seeds go through a chain of
+//! range maps, from soil down to location.
+
+use aoc_common::{input, Answer, Error, ParseError, Rng, Solution};
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+#[derive(Debug, Default)]
+struct MappedRange {
+    start: u64,
+    end: u64,
+    destination_start: u64,
+}
+
+impl MappedRange {
+    fn new(start: u64, end: u64, destination_start: u64) -> Self {
+        Self {
+            start,
+            end,
+            destination_start,
+        }
+    }
+
+    fn in_range(&self, source: u64) -> bool {
+        source >= self.start && source <= self.end
+    }
+
+    fn get_destination(&self, source: u64) -> u64 {
+        self.destination_start + (source - self.start)
+    }
+}
+
+/// The almanac: the seeds and every map, applied in `map_order`.
+#[derive(Debug, Default)]
+pub struct Mapper {
+    seeds: Vec<u64>,
+    map_order: Vec<String>,
+    maps: HashMap<String, Vec<MappedRange>>,
+}
+
+impl Mapper {
+    /// An empty almanac that knows the names of the seven maps.
+    pub fn new() -> Self {
+        let maps = [
+            "seed-to-soil",
+            "soil-to-fertilizer",
+            "fertilizer-to-water",
+            "water-to-light",
+            "light-to-temperature",
+            "temperature-to-humidity",
+            "humidity-to-location",
+        ];
+
+        let collected_maps: HashMap<String, Vec<MappedRange>> = HashMap::new();
+
+        Self {
+            map_order: maps.iter().map(|&s| s.to_owned()).collect(),
+            maps: collected_maps,
+            ..Default::default()
+        }
+    }
+
+    pub fn seeds(&self) -> &[u64] {
+        &self.seeds
+    }
+
+    /// The map names, from `seed-to-soil` to `humidity-to-location`.
+    pub fn map_order(&self) -> &[String] {
+        &self.map_order
+    }
+
+    /// Reads the `seeds:` line and the map sections of an almanac.
+    pub fn parse_data(&mut self, data: &[String]) -> Result<(), ParseError> {
+        // The first row is just a list of the seeds
+        let first = data.first().map_or("", |row| row.as_str());
+        let seed_data: Vec<&str> = first.split_ascii_whitespace().collect();
+        if seed_data.first() != Some(&"seeds:") || seed_data.len() < 2 {
+            return Err(ParseError::at(first, first, "expected `seeds: <ids>`"));
+        }
+        self.seeds = seed_data[1..]
+            .iter()
+            .map(|&id| {
+                id.parse::<u64>()
+                    .map_err(|_| ParseError::at(first, id, "invalid seed id"))
+            })
+            .collect::<Result<_, _>>()?;
+        // Read as `<start> <length>` pairs, no range may run past the last id
+        for (pair, ids) in self
+            .seeds
+            .chunks_exact(2)
+            .zip(seed_data[1..].chunks_exact(2))
+        {
+            if pair[0].checked_add(pair[1]).is_none() {
+                return Err(ParseError::at(first, ids[1], "seed range overflows"));
+            }
+        }
+
+        let mut active_map = "";
+        for (i, row) in data.iter().enumerate().skip(1) {
+            let line = i + 1;
+            if row.trim().is_empty() {
+                continue;
+            } else if !row.trim_start().starts_with(|c: char| c.is_numeric()) {
+                active_map = row.split_ascii_whitespace().next().unwrap_or_default();
+                if !self.map_order.iter().any(|name| name == active_map) {
+                    return Err(ParseError::at(row, active_map, "unknown map").on_line(line));
+                }
+            } else if active_map.is_empty() {
+                return Err(ParseError::at(row, row, "range before any map header").on_line(line));
+            } else {
+                self.populate_map(active_map, row)
+                    .map_err(|e| e.on_line(line))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn populate_map(&mut self, map_name: &str, values: &str) -> Result<(), ParseError> {
+        let data: Vec<u64> = values
+            .split_ascii_whitespace()
+            .map(|val| {
+                val.parse::<u64>()
+                    .map_err(|_| ParseError::at(values, val, "invalid number"))
+            })
+            .collect::<Result<_, _>>()?;
+        let [destination, start, size] = data[..] else {
+            return Err(ParseError::at(
+                values,
+                values,
+                "expected `<destination> <source> <length>`",
+            ));
+        };
+        let end = size
+            .checked_sub(1)
+            .and_then(|size| start.checked_add(size))
+            .ok_or_else(|| ParseError::at(values, values, "range is empty or overflows"))?;
+
+        self.maps
+            .entry(map_name.to_owned())
+            .or_default()
+            .push(MappedRange::new(start, end, destination));
+        Ok(())
+    }
+
+    /// Where `source` ends up in the named map.
+    pub fn find_destination(&self, map_name: &str, source: u64) -> u64 {
+        // Sources not covered by any range map to themselves
+        let Some(map) = self.maps.get(map_name) else {
+            return source;
+        };
+        for map_range in map.iter() {
+            let contains = map_range.in_range(source);
+            if contains {
+                return map_range.get_destination(source);
+            }
+        }
+        source
+    }
+
+    /// The location a seed ends up at after every map.
+    pub fn score_seed(&self, seed_id: u64) -> u64 {
+        let mut current_source = seed_id;
+        for map in &self.map_order {
+            let destination = self.find_destination(map, current_source);
+            current_source = destination;
+        }
+        current_source
+    }
+
+    /// The lowest location of any listed seed, as in part 1.
+    pub fn find_min_individual_seed_score(&self) -> u64 {
+        let mut lowest_score = u64::MAX;
+
+        for &seed in &self.seeds {
+            let seed_score = self.score_seed(seed);
+            if seed_score < lowest_score {
+                lowest_score = seed_score;
+            }
+        }
+        lowest_score
+    }
+
+    /// The lowest location when the seeds are read as `<start> <length>`
+    /// pairs, as in part 2, trying every seed in every range.
+    pub fn find_min_seed_pair_score_brute(&self) -> Result<u64, Error> {
+        if !self.seeds.len().is_multiple_of(2) {
+            return Err(Error::Invalid(format!(
+                "seed ranges need an even number of values, got {}",
+                self.seeds.len()
+            )));
+        }
+        let mut seed_scores: Vec<u64> = Vec::with_capacity(self.seeds.len() / 2);
+        for (count, pair) in (1..).zip(self.seeds.chunks(2)) {
+            let end = pair[0]
+                .checked_add(pair[1])
+                .ok_or_else(|| Error::Invalid(format!("seed range {:?} overflows", pair)))?;
+            let min_score: u64 = (pair[0]..end)
+                .into_par_iter()
+                .map(|seed_id| self.score_seed(seed_id))
+                .min()
+                .unwrap_or(u64::MAX);
+            seed_scores.push(min_score);
+            log::info!("seed range {} of {} done", count, self.seeds.len() / 2);
+        }
+        log::debug!("lowest location per seed range: {:?}", seed_scores);
+        Ok(seed_scores.into_iter().min().unwrap_or(u64::MAX))
+    }
+}
+
+/// An almanac with `size` seed ranges of up to 100 seeds each and every map
+/// split into one to `size + 2` non-overlapping ranges, all below
+/// `100 * size`.
+fn generate_almanac(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let limit = 100 * size as u64;
+
+    let seeds: Vec<String> = (0..size)
+        .map(|_| format!("{} {}", rng.range(0..limit), rng.range(1..101)))
+        .collect();
+    let mut sections = vec![format!("seeds: {}", seeds.join(" "))];
+
+    for name in Mapper::new().map_order {
+        // Pairs of distinct cut points give the non-overlapping source ranges
+        let ranges = rng.range(1..size as u64 + 3) as usize;
+        let mut cuts: Vec<u64> = (0..limit).collect();
+        rng.shuffle(&mut cuts);
+        let mut cuts = cuts[..2 * ranges.min(size * 50)].to_vec();
+        cuts.sort_unstable();
+
+        let mut lines = vec![format!("{} map:", name)];
+        for pair in cuts.chunks(2) {
+            let (start, length) = (pair[0], pair[1] - pair[0]);
+            lines.push(format!("{} {} {}", rng.range(0..limit), start, length));
+        }
+        sections.push(lines.join("\n"));
+    }
+    sections.join("\n\n")
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Mapper;
+
+    const DAY: u8 = 5;
+
+    const DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    const EXAMPLE: Option<&'static str> = Some("test.txt");
+
+    fn parse(&self, file: &str) -> Result<Self::Input, Error> {
+        let mut maps = Mapper::new();
+        maps.parse_data(&input::lines(file))?;
+        Ok(maps)
+    }
+
+    fn part1(&self, maps: &Self::Input) -> Result<Answer, Error> {
+        Ok(maps.find_min_individual_seed_score().into())
+    }
+
+    fn part2(&self, maps: &Self::Input) -> Result<Answer, Error> {
+        Ok(maps.find_min_seed_pair_score_brute()?.into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Result<String, Error> {
+        Ok(generate_almanac(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{testing, Part};
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn mapper_scores_seeds() {
+        let maps = Day05.parse(EXAMPLE).unwrap();
+        assert_eq!(maps.seeds, vec![79, 14, 55, 13]);
+        assert_eq!(maps.find_destination("seed-to-soil", 79), 81);
+        assert_eq!(maps.find_destination("seed-to-soil", 10), 10);
+        assert_eq!(maps.score_seed(13), 35);
+    }
+
+    #[test]
+    fn mapper_rejects_short_ranges() {
+        let mut maps = Mapper::new();
+        let data = input::lines("seeds: 1\n\nseed-to-soil map:\n50 98");
+        let error = maps.parse_data(&data).unwrap_err();
+        assert_eq!(error.line, 4);
+    }
+
+    #[test]
+    fn mapper_rejects_overflowing_seed_ranges() {
+        let mut maps = Mapper::new();
+        let data = input::lines("seeds: 1 2 18446744073709551615 5\n\nseed-to-soil map:\n50 98 2");
+        let error = maps.parse_data(&data).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 33));
+        assert_eq!(error.message, "seed range overflows");
+    }
+
+    #[test]
+    fn part1_example() {
+        testing::assert_answers(&Day05, EXAMPLE, &[(Part::One, "35")]);
+    }
+
+    #[test]
+    fn part2_example() {
+        testing::assert_answers(&Day05, EXAMPLE, &[(Part::Two, "46")]);
+    }
+
+    #[test]
+    fn part2_tries_the_last_seed_of_a_range() {
+        // Seeds 10 to 12, of which only 12 maps anywhere lower
+        let data = Day05
+            .parse("seeds: 10 3\n\nseed-to-soil map:\n0 12 1")
+            .unwrap();
+        assert_eq!(Day05.part2(&data).unwrap().as_str(), "0");
+    }
+
+    /// The lowest location of part 2 worked out on whole ranges, splitting
+    /// them where a map's ranges begin and end, straight from the almanac.
+    fn lowest_location_by_ranges(file: &str) -> u64 {
+        let numbers = |line: &str| -> Vec<u64> {
+            line.split_ascii_whitespace()
+                .filter_map(|n| n.parse().ok())
+                .collect()
+        };
+        let mut sections = file.split("\n\n");
+        let seeds = numbers(sections.next().unwrap());
+        let mut ranges: Vec<(u64, u64)> = seeds
+            .chunks(2)
+            .map(|pair| (pair[0], pair[0] + pair[1]))
+            .collect();
+        for section in sections {
+            let mut mapped = Vec::new();
+            for entry in section.lines().skip(1).map(numbers) {
+                let (destination, source, end) = (entry[0], entry[1], entry[1] + entry[2]);
+                let mut unmapped = Vec::new();
+                for (start, stop) in ranges {
+                    let (low, high) = (start.max(source), stop.min(end));
+                    if low < high {
+                        mapped.push((low - source + destination, high - source + destination));
+                    }
+                    if start < stop.min(source) {
+                        unmapped.push((start, stop.min(source)));
+                    }
+                    if start.max(end) < stop {
+                        unmapped.push((start.max(end), stop));
+                    }
+                }
+                ranges = unmapped;
+            }
+            ranges.extend(mapped);
+        }
+        ranges.iter().map(|&(start, _)| start).min().unwrap()
+    }
+
+    #[test]
+    fn part2_matches_range_splitting_on_generated_input() {
+        assert_eq!(lowest_location_by_ranges(EXAMPLE), 46);
+        for seed in 0..10 {
+            let file = generate_almanac(&mut Rng::new(seed), 5);
+            let maps = Day05.parse(&file).unwrap();
+            assert_eq!(
+                Day05.part2(&maps).unwrap().as_str(),
+                lowest_location_by_ranges(&file).to_string(),
+                "seed {}",
+                seed
+            );
+        }
+    }
+}
@@ -0,0 +1,229 @@
+//! Day 6: Wait For It. Holding a boat's button longer charges it faster but
+//! leaves less time to race.
+
+use aoc_common::{input, Answer, Error, ParseError, Rng, Solution};
+
+/// A race's duration and the record distance to beat.
+#[derive(Debug, Default)]
+pub struct RaceRecord {
+    pub time: u64,
+    pub distance: u64,
+}
+
+pub fn multiply_all_wins(wins: &[u64]) -> u64 {
+    wins.iter().product()
+}
+
+impl RaceRecord {
+    pub fn new(time: u64, distance: u64) -> Self {
+        Self { time, distance }
+    }
+
+    /// How many whole-millisecond charge times beat the record.
+    pub fn evaluate_charge_times(&self) -> u64 {
+        let mut wins: u64 = 0;
+        for charge_time in 0..self.time {
+            let remaining_time = self.time - charge_time;
+            if remaining_time * charge_time > self.distance {
+                wins += 1
+            }
+        }
+        wins
+    }
+}
+
+const LABELS: [&str; 2] = ["Time:", "Distance:"];
+
+/// The values on the time and distance rows, without their labels.
+fn split_rows(data: &[String]) -> Result<Vec<Vec<&str>>, ParseError> {
+    for (i, label) in LABELS.iter().enumerate() {
+        let row = data.get(i).map_or("", |row| row.as_str());
+        if !row.starts_with(label) {
+            let message = format!("expected `{} <values>`", label);
+            return Err(ParseError::at(row, row, message).on_line(i + 1));
+        }
+    }
+    let rows: Vec<_> = data
+        .iter()
+        .map(|row| row.split_ascii_whitespace().skip(1).collect::<Vec<&str>>())
+        .collect();
+    if rows[0].len() != rows[1].len() {
+        let message = "expected one distance per race time";
+        return Err(ParseError::at(&data[1], &data[1], message).on_line(2));
+    }
+    Ok(rows)
+}
+
+/// One record per column of the race sheet, as in part 1.
+pub fn read_records(data: &[String]) -> Result<Vec<RaceRecord>, ParseError> {
+    let rows = split_rows(data)?;
+    let parse = |i: usize, value: &str| {
+        value
+            .parse::<u64>()
+            .map_err(|_| ParseError::at(&data[i], value, "invalid number").on_line(i + 1))
+    };
+    let mut records: Vec<RaceRecord> = Vec::new();
+    for (i, time) in rows[0].iter().enumerate() {
+        records.push(RaceRecord::new(parse(0, time)?, parse(1, rows[1][i])?))
+    }
+    Ok(records)
+}
+
+/// The single race given by joining each row's digits, as in part 2.
+pub fn fix_kerning(data: &[String]) -> Result<RaceRecord, ParseError> {
+    let rows = split_rows(data)?;
+    let joined: Vec<_> = rows
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let value = line.iter().fold(String::new(), |acc, entry| acc + entry);
+            value.parse::<u64>().map_err(|_| ParseError {
+                text: value.clone(),
+                ..ParseError::at(
+                    &data[i],
+                    line.first().unwrap_or(&""),
+                    "invalid joined number",
+                )
+                .on_line(i + 1)
+            })
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(RaceRecord::new(joined[0], joined[1]))
+}
+
+/// Both readings of a race sheet.
+pub struct Races {
+    records: Vec<RaceRecord>,
+    single_race: RaceRecord,
+}
+
+/// A race sheet with `size` races, each record beatable by at least one
+/// charge time.
+///
+/// Part 2 joins every race into one and counts its charge times one by one,
+/// so at most four races are generated.
+fn generate_sheet(rng: &mut Rng, size: usize) -> String {
+    let races = size.clamp(1, 4);
+    let mut times = Vec::with_capacity(races);
+    let mut distances = Vec::with_capacity(races);
+    for _ in 0..races {
+        let time = rng.range(2..100);
+        let best = (time / 2) * (time - time / 2);
+        times.push(time);
+        distances.push(rng.range(0..best));
+    }
+    let row = |values: &[u64]| {
+        let values: Vec<String> = values.iter().map(|v| format!("{:>5}", v)).collect();
+        values.concat()
+    };
+    format!("Time:     {}\nDistance: {}", row(&times), row(&distances))
+}
+
+impl Races {
+    pub fn records(&self) -> &[RaceRecord] {
+        &self.records
+    }
+
+    pub fn single_race(&self) -> &RaceRecord {
+        &self.single_race
+    }
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Races;
+
+    const DAY: u8 = 6;
+
+    const DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    const EXAMPLE: Option<&'static str> = Some("test.txt");
+
+    fn parse(&self, file: &str) -> Result<Self::Input, Error> {
+        let data = input::lines(file);
+        Ok(Races {
+            records: read_records(&data)?,
+            single_race: fix_kerning(&data)?,
+        })
+    }
+
+    fn part1(&self, races: &Self::Input) -> Result<Answer, Error> {
+        let wins: Vec<u64> = races
+            .records
+            .iter()
+            .map(|record| record.evaluate_charge_times())
+            .collect();
+        Ok(multiply_all_wins(&wins).into())
+    }
+
+    fn part2(&self, races: &Self::Input) -> Result<Answer, Error> {
+        Ok(races.single_race.evaluate_charge_times().into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Result<String, Error> {
+        Ok(generate_sheet(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{testing, Part};
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn race_record_counts_wins() {
+        assert_eq!(RaceRecord::new(7, 9).evaluate_charge_times(), 4);
+        assert_eq!(RaceRecord::new(30, 200).evaluate_charge_times(), 9);
+    }
+
+    #[test]
+    fn fix_kerning_joins_columns() {
+        let record = fix_kerning(&input::lines(EXAMPLE)).unwrap();
+        assert_eq!((record.time, record.distance), (71530, 940200));
+    }
+
+    #[test]
+    fn part1_example() {
+        testing::assert_answers(&Day06, EXAMPLE, &[(Part::One, "288")]);
+    }
+
+    #[test]
+    fn part2_example() {
+        testing::assert_answers(&Day06, EXAMPLE, &[(Part::Two, "71503")]);
+    }
+
+    /// Counts the winning charge times from the roots of
+    /// `charge * (time - charge) = distance` instead of trying each one.
+    fn closed_form(record: &RaceRecord) -> u64 {
+        let (time, distance) = (record.time, record.distance);
+        let root = ((time * time - 4 * distance) as f64).sqrt();
+        let mut low = ((time as f64 - root) / 2.0).floor() as u64;
+        low = low.saturating_sub(1);
+        while low * (time - low) <= distance {
+            low += 1;
+        }
+        time + 1 - 2 * low
+    }
+
+    #[test]
+    fn charge_times_match_closed_form_on_generated_input() {
+        for seed in 0..20 {
+            let races = Day06
+                .parse(&generate_sheet(&mut Rng::new(seed), 3))
+                .unwrap();
+            for record in races.records.iter().chain([&races.single_race]) {
+                assert_eq!(
+                    record.evaluate_charge_times(),
+                    closed_form(record),
+                    "seed {}: {:?}",
+                    seed,
+                    record
+                );
+            }
+        }
+    }
+}